@@ -1,4 +1,7 @@
-use crate::{domain::riddle_part::RiddlePart, Configuration, Driver};
+use crate::{
+    domain::{riddle_part::RiddlePart, submission_outcome::SubmissionOutcome},
+    Configuration, Driver,
+};
 use anyhow::Result;
 
 /// Downloads the input from Advent of Code servers
@@ -51,6 +54,11 @@ pub fn get_input(day: usize, year: usize, token: Option<&str>) -> Result<String>
 /// * `riddle_part` - either 1 or 2 indicating, respectively, part one and two of the riddle
 /// * `token` - optionally, the token used to authenticate you against AOC servers
 ///
+/// # Errors
+///
+/// Returns an error if the answer looks like a mistake, e.g. it is the same
+/// as the answer to the first part. Such an answer is not sent to the servers.
+///
 /// # Examples
 ///
 /// ```
@@ -83,6 +91,10 @@ pub fn submit(
         2 => RiddlePart::Two,
         _ => RiddlePart::One,
     };
-    driver.submit_answer(year, day, part, answer.to_owned())?;
+    let outcome = driver.submit_answer(year, day, part, answer.to_owned(), false)?;
+    if let SubmissionOutcome::Suspicious(_) = outcome {
+        anyhow::bail!("{}", outcome);
+    }
+    println!("{}", outcome);
     Ok(())
 }
@@ -1,28 +1,48 @@
+mod cli_cache_subcommand;
 mod cli_command;
 mod cli_config_subcommand;
 mod cli_interface;
+mod cli_output_file;
 
 use std::io::Write;
 use std::path::PathBuf;
+use std::process::ExitCode;
 
 use anyhow::{Context, Result};
-use chrono::Datelike;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
 use crate::application::cli::{
-    cli_command::{CliCommand, RiddleArgs, TokenArgs},
+    cli_cache_subcommand::CacheSubcommand,
+    cli_command::{
+        CliCommand, DescriptionArgs, GlobalArgs, RiddleArgs, RiddleRangeArgs, TokenArgs,
+    },
     cli_config_subcommand::ConfigSubcommand,
     cli_interface::CliInterface,
+    cli_output_file::write_to_file,
 };
-use crate::domain::{riddle_date::RiddleDate, riddle_part::RiddlePart};
+use crate::application::tui;
+use crate::domain::{
+    answer_check::answer_from_output, day_range::DayRange, description_parts::DescriptionParts,
+    duration_string::DurationString, event::Event, riddle_date::RiddleDate,
+    riddle_part::RiddlePart, submission_outcome::SubmissionOutcome,
+    submission_preview::SubmissionPreview, submission_status::SubmissionStatus,
+};
+use crate::infrastructure::cache_inventory::CacheFilter;
+use crate::infrastructure::calendar_image::{self, ImageFormat};
 use crate::infrastructure::cli_display::CliDisplay;
+use crate::infrastructure::configuration_keys::CONFIGURATION_KEYS;
+use crate::infrastructure::path_template::PathTemplate;
 use crate::{Configuration, Driver};
 
 pub struct ElvCli {}
 
 impl ElvCli {
-    pub fn run() {
+    /// Runs the command given on the command line and returns the status the
+    /// process exits with
+    pub fn run() -> ExitCode {
         let cli = CliInterface::parse();
+        let global_args = cli.global_args;
+        let mut exit_code = ExitCode::SUCCESS;
 
         match cli.command {
             CliCommand::Input {
@@ -30,142 +50,787 @@ impl ElvCli {
                 token_args,
                 out,
                 no_file,
+                overwrite,
                 print,
-            } => handle_input_command(riddle_args, token_args, out, no_file, print),
+            } => handle_input_command(
+                &global_args,
+                riddle_args,
+                token_args,
+                out,
+                no_file,
+                overwrite,
+                print,
+            ),
             CliCommand::Submit {
                 riddle_args,
                 token_args,
-                part,
+                mut part,
                 answer,
-            } => {
-                handle_submit_command(riddle_args, token_args, part, answer);
-            }
+                answer_file,
+                from_output,
+                force,
+                dry_run,
+                wait,
+            } => match read_answer(answer, answer_file, from_output, &mut part) {
+                Ok(answer) => {
+                    exit_code = handle_submit_command(
+                        &global_args,
+                        riddle_args,
+                        token_args,
+                        part,
+                        answer,
+                        SubmitMode {
+                            force,
+                            dry_run,
+                            wait,
+                        },
+                    )
+                }
+                Err(e) => {
+                    eprintln!("❌ {:#}", e);
+                    exit_code = ExitCode::FAILURE;
+                }
+            },
             CliCommand::Description {
                 token,
                 riddle_args,
-                width,
-            } => handle_description_command(token, riddle_args, width),
+                description_args,
+                out,
+                overwrite,
+            } => handle_description_command(
+                &global_args,
+                token,
+                riddle_args,
+                description_args,
+                out,
+                overwrite,
+            ),
             CliCommand::Leaderboard { token_args, year } => {
-                handle_get_leaderboard(token_args, year)
+                handle_get_leaderboard(&global_args, token_args, year)
             }
             CliCommand::PrivateLeaderboard {
                 token_args,
                 leaderboard_id,
                 year,
-            } => handle_get_private_leaderboard(token_args, &leaderboard_id, year),
-            CliCommand::Stars { year } => handle_get_stars(year),
-            CliCommand::ClearCache => handle_clear_cache_command(),
-            CliCommand::ListDirs => handle_list_dirs_command(),
+            } => handle_get_private_leaderboard(&global_args, token_args, leaderboard_id, year),
+            CliCommand::Stars {
+                year,
+                plain,
+                export,
+                out,
+            } => handle_get_stars(&global_args, year, plain, export.zip(out)),
+            CliCommand::Prefetch {
+                token_args,
+                year,
+                leaderboard_id,
+            } => handle_prefetch_command(&global_args, token_args, year, leaderboard_id),
+            CliCommand::Sync { token_args, year } => {
+                handle_sync_command(&global_args, token_args, year)
+            }
+            CliCommand::Test {
+                riddle_args,
+                token_args,
+                part,
+                command,
+            } => {
+                exit_code =
+                    handle_test_command(&global_args, riddle_args, token_args, part, command)
+            }
+            CliCommand::Verify {
+                token_args,
+                year,
+                day,
+                command,
+            } => exit_code = handle_verify_command(&global_args, token_args, year, day, command),
+            CliCommand::Tui {
+                riddle_args,
+                token_args,
+                leaderboard_id,
+            } => handle_tui_command(&global_args, riddle_args, token_args, leaderboard_id),
+            CliCommand::ClearCache => handle_clear_cache_command(&global_args),
+            CliCommand::Cache { cmd } => handle_cache_command(&global_args, cmd),
+            CliCommand::ListDirs => handle_list_dirs_command(&global_args),
             CliCommand::Config { cmd } => match cmd {
                 ConfigSubcommand::List {} => handle_get_config(),
+                ConfigSubcommand::Keys {} => handle_config_keys(),
+                ConfigSubcommand::Get { key } => exit_code = handle_get_config_key(&key),
                 ConfigSubcommand::Set { key, value } => handle_set_config(&key, value),
+                ConfigSubcommand::Unset { key } => handle_unset_config(&key),
+                ConfigSubcommand::Edit {} => handle_edit_config(),
             },
             CliCommand::Token { token } => handle_token_comamand(token),
         }
+        return exit_code;
+
+        /// Gets the answer from the argument, the standard input or the file
+        ///
+        /// When the answer comes from the file or the piped output,
+        /// the first positional argument is the part of the riddle.
+        fn read_answer(
+            answer: Option<String>,
+            answer_file: Option<PathBuf>,
+            from_output: bool,
+            part: &mut Option<RiddlePart>,
+        ) -> Result<String> {
+            let read_stdin = || {
+                std::io::read_to_string(std::io::stdin())
+                    .context("Failed to read the answer from the standard input")
+            };
+            let text = match (answer, answer_file) {
+                (Some(answer), _) if answer == "-" => read_stdin()?,
+                (None, None) => read_stdin()?,
+                (Some(answer), None) if !from_output => answer,
+                (part_argument, answer_file) => {
+                    if let Some(part_argument) = part_argument {
+                        if part.is_some() {
+                            anyhow::bail!("Unexpected argument `{}`. The answer is already read from the input", part_argument);
+                        }
+                        *part = Some(
+                            RiddlePart::from_str(&part_argument, true)
+                                .map_err(anyhow::Error::msg)?,
+                        );
+                    }
+                    match answer_file {
+                        Some(path) => std::fs::read_to_string(&path).with_context(|| {
+                            format!("Failed to read the answer from `{}`", path.display())
+                        })?,
+                        None => read_stdin()?,
+                    }
+                }
+            };
+            if from_output {
+                return answer_from_output(&text).context("There is no answer in the output");
+            }
+            Ok(text)
+        }
 
         fn handle_submit_command(
+            global_args: &GlobalArgs,
             riddle_args: RiddleArgs,
             token_args: TokenArgs,
-            mut part: Option<RiddlePart>,
+            part: Option<RiddlePart>,
             answer: String,
-        ) {
-            let driver = get_driver(Some(token_args), None);
-            let (year, day) = match determine_date(riddle_args) {
+            mode: SubmitMode,
+        ) -> ExitCode {
+            let driver = get_driver(global_args, Some(token_args), None);
+            let (year, day) = match determine_date(&driver.configuration, riddle_args) {
                 Ok(res) => res,
                 Err(e) => {
                     eprintln!("❌ {}", e.to_string());
-                    return;
+                    return ExitCode::FAILURE;
                 }
             };
-            if part.is_none() {
-                part = Some(
-                    driver
-                        .guess_riddle_part(year, day)
-                        .context("❌ Could not guess the riddle part. Provide it manually as an argument")
-                        .unwrap(),
+            let is_part_guessed = part.is_none();
+            let part = match part {
+                Some(part) => part,
+                None => match driver.guess_riddle_part(year, day) {
+                    Ok(part) => part,
+                    Err(e) => {
+                        eprintln!("❌ Could not guess the riddle part. Provide it manually as an argument: {:#}", e);
+                        return ExitCode::FAILURE;
+                    }
+                },
+            };
+            if mode.dry_run {
+                return match driver.preview_submission(year, day, part, answer, mode.force) {
+                    Ok(preview) => {
+                        print_submission_preview(&preview, is_part_guessed);
+                        ExitCode::SUCCESS
+                    }
+                    Err(e) => {
+                        eprintln!("❌ {:#}", e);
+                        ExitCode::FAILURE
+                    }
+                };
+            }
+            loop {
+                let outcome =
+                    match driver.submit_answer(year, day, part.clone(), answer.clone(), mode.force)
+                    {
+                        Ok(outcome) => outcome,
+                        Err(e) => {
+                            eprintln!("❌ Failed to submit the answer. {}", e.to_string());
+                            return ExitCode::FAILURE;
+                        }
+                    };
+                let lockout = match &outcome {
+                    SubmissionOutcome::TooSoon(wait_time) => Some(*wait_time),
+                    SubmissionOutcome::Submitted(result)
+                        if result.status == SubmissionStatus::TooSoon =>
+                    {
+                        chrono::Duration::from_std(result.wait_time).ok()
+                    }
+                    _ => None,
+                };
+                match lockout {
+                    Some(lockout) if mode.wait && lockout > chrono::Duration::zero() => {
+                        wait_with_countdown(lockout)
+                    }
+                    Some(_) if mode.wait => {
+                        print_submission_outcome(&outcome);
+                        eprintln!("❌ The servers did not say how long to wait, so the answer is not submitted again");
+                        return ExitCode::FAILURE;
+                    }
+                    _ => {
+                        print_submission_outcome(&outcome);
+                        return submission_exit_code(&outcome);
+                    }
+                }
+            }
+        }
+
+        /// Sleeps until the wait is over, showing the remaining time
+        fn wait_with_countdown(wait_time: chrono::Duration) {
+            // A second more, so the servers surely count the wait as over
+            let end = chrono::Utc::now() + wait_time + chrono::Duration::seconds(1);
+            loop {
+                let remaining = end - chrono::Utc::now();
+                if remaining <= chrono::Duration::zero() {
+                    break;
+                }
+                eprint!(
+                    "\r\x1b[2K⏳ You need to wait before submitting. Submitting in {}...",
+                    DurationString::new(remaining)
+                );
+                let _ = std::io::stderr().flush();
+                std::thread::sleep(
+                    remaining
+                        .to_std()
+                        .unwrap_or_default()
+                        .min(std::time::Duration::from_secs(1)),
                 );
             }
-            match driver.submit_answer(year, day, part.unwrap(), answer) {
-                Ok(_) => {}
-                Err(e) => eprint!("❌ Failed to submit the answer. {}", e.to_string()),
+            eprintln!("\r\x1b[2K⏳ Submitting the answer...");
+        }
+
+        fn handle_test_command(
+            global_args: &GlobalArgs,
+            riddle_args: RiddleArgs,
+            token_args: TokenArgs,
+            part: Option<RiddlePart>,
+            command: Option<String>,
+        ) -> ExitCode {
+            let mut driver = get_driver(global_args, Some(token_args), None);
+            if command.is_some() {
+                driver.configuration.solutions.command = command;
+            }
+            if driver.configuration.solutions.command.is_none() {
+                eprintln!("❌ No solution to run. Pass it with `--command` or set it with `elv config set solutions.command <COMMAND>`");
+                return ExitCode::FAILURE;
+            }
+            let (year, day) = match determine_date(&driver.configuration, riddle_args) {
+                Ok(res) => res,
+                Err(e) => {
+                    eprintln!("❌ {}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let examples = match driver.riddle_examples(year, day) {
+                Ok(examples) => examples
+                    .into_iter()
+                    .filter(|example| part.as_ref().is_none_or(|part| example.part == *part))
+                    .collect::<Vec<_>>(),
+                Err(e) => {
+                    eprintln!("❌ Failed to get the examples: {:#}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            if examples.is_empty() {
+                eprintln!(
+                    "❌ No examples found for {}-{:02}. Add them to `{}`",
+                    year,
+                    day,
+                    PathTemplate::new(&driver.configuration.solutions.examples)
+                        .render(year, day)
+                        .display()
+                );
+                return ExitCode::FAILURE;
+            }
+
+            let mut passed = 0;
+            for (index, example) in examples.iter().enumerate() {
+                let label = format!(
+                    "Part {} example {} (from {})",
+                    example.part,
+                    index + 1,
+                    example.source
+                );
+                match driver.run_solution(year, day, &example.part, &example.input) {
+                    Ok(answer) if answer == example.answer => {
+                        passed += 1;
+                        println!("✅ {}: {}", label, answer);
+                    }
+                    Ok(answer) => println!(
+                        "❌ {}: expected {} but got {}",
+                        label, example.answer, answer
+                    ),
+                    Err(e) => println!("❌ {}: {:#}", label, e),
+                }
+            }
+            println!("\n{}/{} examples passed", passed, examples.len());
+            if passed < examples.len() {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+
+        /// How `elv submit` handles the answer
+        struct SubmitMode {
+            force: bool,
+            dry_run: bool,
+            wait: bool,
+        }
+
+        fn handle_verify_command(
+            global_args: &GlobalArgs,
+            token_args: TokenArgs,
+            year: Option<usize>,
+            days: Option<DayRange>,
+            command: Option<String>,
+        ) -> ExitCode {
+            let mut driver = get_driver(global_args, Some(token_args), None);
+            if command.is_some() {
+                driver.configuration.solutions.command = command;
+            }
+            if driver.configuration.solutions.command.is_none() {
+                eprintln!("❌ No solution to run. Pass it with `--command` or set it with `elv config set solutions.command <COMMAND>`");
+                return ExitCode::FAILURE;
+            }
+            let year = year.unwrap_or_else(|| determine_year(&driver.configuration) as usize);
+            let days = match driver.released_days(year, days) {
+                Ok(days) => days,
+                Err(e) => {
+                    eprintln!("❌ {:#}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            println!(
+                "{:<10} {:<5} {:<8} {:>9}  Details",
+                "Riddle", "Part", "Result", "Time"
+            );
+            let (mut passed, mut failed) = (0, 0);
+            for day in days {
+                let known_answers = match driver.known_answers(year, day) {
+                    Ok(answers) if answers.is_empty() => continue,
+                    Ok(answers) => answers,
+                    Err(e) => {
+                        eprintln!("❌ {}-{:02}: {:#}", year, day, e);
+                        failed += 1;
+                        continue;
+                    }
+                };
+                let input = match driver.input(year, day) {
+                    Ok(input) => input,
+                    Err(e) => {
+                        eprintln!("❌ {}-{:02}: failed to get the input: {:#}", year, day, e);
+                        failed += known_answers.len();
+                        continue;
+                    }
+                };
+                for (part, expected) in known_answers {
+                    let start = std::time::Instant::now();
+                    let result = driver.run_solution(year, day, &part, &input);
+                    let elapsed = format!("{:.2}s", start.elapsed().as_secs_f64());
+                    let (status, details) = match result {
+                        Ok(answer) if answer == expected => {
+                            passed += 1;
+                            ("✅ pass", String::new())
+                        }
+                        Ok(answer) => {
+                            failed += 1;
+                            (
+                                "❌ fail",
+                                format!("expected {} but got {}", expected, answer),
+                            )
+                        }
+                        Err(e) => {
+                            failed += 1;
+                            ("❌ error", format!("{:#}", e).replace('\n', " "))
+                        }
+                    };
+                    println!(
+                        "{:<10} {:<5} {:<8} {:>9}  {}",
+                        format!("{}-{:02}", year, day),
+                        part.to_string(),
+                        status,
+                        elapsed,
+                        details
+                    );
+                }
+            }
+            println!("\n{} passed, {} failed", passed, failed);
+            if failed > 0 {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+
+        fn print_submission_preview(preview: &SubmissionPreview, is_part_guessed: bool) {
+            let submission = &preview.submission;
+            println!("🔍 Dry run - nothing is submitted\n");
+            println!(
+                "Riddle: {}-{:02}, part {}{}",
+                submission.year,
+                submission.day,
+                submission.part,
+                if is_part_guessed {
+                    " (guessed from the description)"
+                } else {
+                    ""
+                }
+            );
+            println!("Answer: {}\n", submission.answer);
+            match &preview.known_outcome {
+                Some(outcome) => {
+                    println!("The answer would not be sent. Instead:");
+                    print_submission_outcome(outcome);
+                }
+                None => println!(
+                    "The answer would be sent with this request:\n\n{}",
+                    preview.request
+                ),
+            }
+        }
+
+        /// Fails when the answer was not sent or the servers did not check it
+        fn submission_exit_code(outcome: &SubmissionOutcome) -> ExitCode {
+            match outcome {
+                SubmissionOutcome::TooSoon(_) | SubmissionOutcome::Suspicious(_) => {
+                    ExitCode::FAILURE
+                }
+                SubmissionOutcome::Submitted(result)
+                    if result.status == SubmissionStatus::TooSoon =>
+                {
+                    ExitCode::FAILURE
+                }
+                _ => ExitCode::SUCCESS,
+            }
+        }
+
+        fn print_submission_outcome(outcome: &SubmissionOutcome) {
+            match outcome {
+                SubmissionOutcome::AlreadySolved(result) => {
+                    eprintln!("🎉  You already submitted the correct answer for this part. Here is the result from last time...\n\n");
+                    println!("{}", result.message);
+                }
+                SubmissionOutcome::Repeated { result, wait_time } => {
+                    eprintln!("♻️  You submitted this answer before and the result was...\n\n");
+                    println!("{}", result.message);
+                    if let Some(wait_time) = wait_time {
+                        eprintln!(
+                            "\n🌡️  You still need to wait {} before another submission.",
+                            DurationString::new(*wait_time)
+                        );
+                    }
+                }
+                SubmissionOutcome::TooSoon(_) => eprintln!("🌡️  {}", outcome),
+                SubmissionOutcome::Suspicious(warnings) => {
+                    eprintln!("🛑 The answer was not submitted, because it looks like a mistake:");
+                    for warning in warnings {
+                        eprintln!("   ⚠️  {}", warning);
+                    }
+                    eprintln!("Pass `--force` to submit it anyway.");
+                }
+                SubmissionOutcome::Submitted(result) => {
+                    eprintln!("Your submission result...\n\n");
+                    println!("{}", result.message);
+                }
             }
         }
 
         fn handle_input_command(
-            riddle_args: RiddleArgs,
+            global_args: &GlobalArgs,
+            riddle_args: RiddleRangeArgs,
             token_args: TokenArgs,
-            out: PathBuf,
+            out: Option<PathBuf>,
             no_file: bool,
+            overwrite: bool,
             print: bool,
         ) {
-            let driver = get_driver(Some(token_args), None);
-            let (year, day) = match determine_date(riddle_args) {
+            let driver = get_driver(global_args, Some(token_args), None);
+            let configured_template = out
+                .map(|out| out.to_string_lossy().into_owned())
+                .or_else(|| driver.configuration.project.input.clone());
+            let riddle_args = match riddle_args.single_riddle() {
+                Some(single_riddle_args) => single_riddle_args,
+                None => {
+                    let template = PathTemplate::new(
+                        &configured_template
+                            .unwrap_or_else(|| "inputs/{year}/day{day:02}.txt".to_owned()),
+                    );
+                    return handle_many_days(
+                        &driver,
+                        &riddle_args,
+                        &template,
+                        None,
+                        no_file,
+                        overwrite,
+                        |(year, day)| {
+                            let input = driver.input(year, day)?;
+                            if print {
+                                println!("{}", input);
+                            }
+                            Ok(input)
+                        },
+                    );
+                }
+            };
+            let (year, day) = match determine_date(&driver.configuration, riddle_args) {
                 Ok(res) => res,
                 Err(e) => {
                     eprintln!("❌ {}", e.to_string());
                     return;
                 }
             };
-            match driver.input(year, day) {
-                Ok(input) => {
-                    if print {
-                        println!("{}", input);
-                    }
-                    if !no_file {
-                        if let Some(parent) = out.parent() {
-                            std::fs::create_dir_all(parent).expect(
-                            format!("Failed to create the directory {}\nYou can still get the input if you print it with the --print flag",
-                            out.to_str().unwrap())
-                                .as_str()
-                        );
-                        }
-
-                        let mut file = std::fs::File::create(&out).expect(
-                        format!("Failed to create the file `{}`\nYou can still get the input if you print it with the --print flag",
-                        out.to_str().unwrap()).as_str()
-                    );
-
-                        match file.write_all(input.as_bytes()) {
-                            Ok(_) => eprintln!("✅ Input written to `{}`", out.to_str().unwrap()),
-                            Err(_) => panic!(concat!(
-                                "❌ Failed to write the input to the file. ",
-                                "You can still get the input if you print it with the --print flag"
-                            )),
-                        }
-                    }
+            let input = match driver.input(year, day) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("❌ Error when getting the input:\n\t{}", e.to_string());
+                    return;
                 }
-                Err(e) => eprintln!("❌ Error when getting the input:\n\t{}", e.to_string()),
+            };
+            if print {
+                println!("{}", input);
+            }
+            if no_file {
+                return;
+            }
+            let template =
+                PathTemplate::new(&configured_template.unwrap_or_else(|| "input".to_owned()));
+            let result = driver
+                .riddle_path(&template, year, day, None)
+                .and_then(|out| write_to_file(&out, &input, overwrite).map(|_| out));
+            match result {
+                Ok(out) => eprintln!("✅ Input written to `{}`", out.display()),
+                Err(e) => eprintln!(
+                    "❌ {:#}\nYou can still get the input if you print it with the --print flag",
+                    e
+                ),
             }
         }
 
         fn handle_description_command(
+            global_args: &GlobalArgs,
             token_args: TokenArgs,
-            riddle_args: RiddleArgs,
-            width: usize,
+            riddle_args: RiddleRangeArgs,
+            description_args: DescriptionArgs,
+            out: Option<PathBuf>,
+            overwrite: bool,
         ) {
-            let driver = get_driver(Some(token_args), Some(width));
-            let (year, day) = match determine_date(riddle_args) {
+            let mut driver = get_driver(global_args, Some(token_args), description_args.width);
+            driver.configuration.cli.rich_descriptions |=
+                description_args.rich || description_args.easter_eggs;
+            driver.configuration.cli.easter_eggs |= description_args.easter_eggs;
+            let parts = description_args.parts();
+            let part = match &parts {
+                DescriptionParts::Only(part) => Some(part.clone()),
+                _ => None,
+            };
+            let get_description = |year, day| {
+                if description_args.refresh {
+                    driver.refresh_description(year, day)?;
+                }
+                if description_args.raw {
+                    driver.get_raw_description(year, day, &parts)
+                } else {
+                    driver.get_description(year, day, &parts)
+                }
+            };
+            let is_written_to_file = out.is_some() || riddle_args.single_riddle().is_none();
+            if is_written_to_file {
+                colored::control::set_override(false);
+            }
+            let riddle_args = match riddle_args.single_riddle() {
+                Some(single_riddle_args) => single_riddle_args,
+                None => {
+                    let template = PathTemplate::new(
+                        &out.map(|out| out.to_string_lossy().into_owned())
+                            .or_else(|| driver.configuration.project.description.clone())
+                            .unwrap_or_else(|| "descriptions/{year}/day{day:02}.txt".to_owned()),
+                    );
+                    return handle_many_days(
+                        &driver,
+                        &riddle_args,
+                        &template,
+                        part.as_ref(),
+                        false,
+                        overwrite,
+                        |(year, day)| get_description(year, day),
+                    );
+                }
+            };
+            let (year, day) = match determine_date(&driver.configuration, riddle_args) {
                 Ok(res) => res,
                 Err(e) => {
                     eprintln!("❌ {}", e.to_string());
                     return;
                 }
             };
-            match driver.get_description(year, day) {
-                Ok(description) => println!("{}", description),
-                Err(e) => eprintln!("Error when getting the description: {}", e.to_string()),
+            let description = match get_description(year, day) {
+                Ok(description) => description,
+                Err(e) => {
+                    eprintln!("Error when getting the description: {}", e.to_string());
+                    return;
+                }
+            };
+            let Some(out) = out else {
+                println!("{}", description);
+                return;
+            };
+            let template = PathTemplate::new(&out.to_string_lossy());
+            let result = driver
+                .riddle_path(&template, year, day, part.as_ref())
+                .and_then(|out| write_to_file(&out, &description, overwrite).map(|_| out));
+            match result {
+                Ok(out) => eprintln!("✅ Description written to `{}`", out.display()),
+                Err(e) => eprintln!("❌ {:#}", e),
+            }
+        }
+
+        /// Gets the riddles for a range of days one by one, writing them
+        /// to the files following the template
+        fn handle_many_days(
+            driver: &Driver,
+            riddle_args: &RiddleRangeArgs,
+            template: &PathTemplate,
+            part: Option<&RiddlePart>,
+            no_file: bool,
+            overwrite: bool,
+            get_riddle: impl Fn((usize, usize)) -> Result<String>,
+        ) {
+            if !no_file && !template.depends_on_day() && !template.depends_on_title() {
+                eprintln!("❌ The path `{}` is the same for every day. Use the {{day}} or {{day:02}} placeholders in it", template.render(0, 0).display());
+                return;
+            }
+            let year = riddle_args
+                .year
+                .unwrap_or_else(|| determine_year(&driver.configuration) as usize);
+            let days = match driver.released_days(year, riddle_args.day_range()) {
+                Ok(days) if days.is_empty() => {
+                    eprintln!("❌ None of the selected riddles is released yet");
+                    return;
+                }
+                Ok(days) => days,
+                Err(e) => {
+                    eprintln!("❌ {:#}", e);
+                    return;
+                }
+            };
+
+            for (index, &day) in days.iter().enumerate() {
+                let progress = format!("[{:>2}/{}] {}-{:02}", index + 1, days.len(), year, day);
+                let result = get_riddle((year, day)).and_then(|riddle| {
+                    if no_file {
+                        return Ok("done".to_owned());
+                    }
+                    let out = driver.riddle_path(template, year, day, part)?;
+                    write_to_file(&out, &riddle, overwrite)?;
+                    Ok(format!("written to `{}`", out.display()))
+                });
+                match result {
+                    Ok(message) => eprintln!("{} ✅ {}", progress, message),
+                    Err(e) => eprintln!("{} ❌ {:#}", progress, e),
+                }
+            }
+        }
+
+        fn handle_tui_command(
+            global_args: &GlobalArgs,
+            riddle_args: RiddleArgs,
+            token_args: TokenArgs,
+            leaderboard_id: Option<String>,
+        ) {
+            // The description takes 60% of the screen width, minus the borders
+            let description_width = ratatui::crossterm::terminal::size()
+                .map(|(columns, _)| (columns as usize * 60 / 100).saturating_sub(2))
+                .ok();
+            let driver = get_driver(global_args, Some(token_args), description_width);
+            // The dashboard cannot show the terminal colour codes
+            colored::control::set_override(false);
+            let (year, day) = match (riddle_args.year, riddle_args.day) {
+                (None, None) => determine_date(&driver.configuration, riddle_args)
+                    .unwrap_or_else(|_| (determine_year(&driver.configuration) as usize, 1)),
+                _ => match determine_date(&driver.configuration, riddle_args) {
+                    Ok(res) => res,
+                    Err(e) => {
+                        eprintln!("❌ {}", e);
+                        return;
+                    }
+                },
+            };
+            let leaderboard_id = leaderboard_id
+                .or_else(|| driver.configuration.project.leaderboards.first().cloned());
+            if let Err(e) = tui::run(&driver, year, day, leaderboard_id) {
+                eprintln!("❌ {:#}", e);
             }
         }
-        fn handle_clear_cache_command() {
-            let driver = get_driver(None, None);
+
+        fn handle_clear_cache_command(global_args: &GlobalArgs) {
+            let driver = get_driver(global_args, None, None);
             match driver.clear_cache() {
                 Ok(_) => eprintln!("✅ Cache cleared"),
                 Err(e) => panic!("❌ error when clearing cache: {}", e.to_string()),
             }
         }
 
-        fn handle_list_dirs_command() {
-            let driver = get_driver(None, None);
+        fn handle_cache_command(global_args: &GlobalArgs, cmd: CacheSubcommand) {
+            let driver = get_driver(global_args, None, None);
+            match cmd {
+                CacheSubcommand::List { filter } => {
+                    match driver.list_cache(&CacheFilter::from(filter)) {
+                        Ok(entries) => println!("{}", entries.cli_fmt(&driver.configuration)),
+                        Err(e) => eprintln!("❌ Failure: {:#}", e),
+                    }
+                }
+                CacheSubcommand::Show {
+                    kind,
+                    year,
+                    day,
+                    leaderboard_id,
+                } => {
+                    let filter = CacheFilter {
+                        kinds: vec![kind],
+                        year: Some(year),
+                        day,
+                        leaderboard_id,
+                    };
+                    match driver.show_cache_entry(&filter) {
+                        Ok(contents) => println!("{}", contents),
+                        Err(e) => eprintln!("❌ Failure: {:#}", e),
+                    }
+                }
+                CacheSubcommand::Rm { filter, all: _ } => {
+                    match driver.remove_cache_entries(&CacheFilter::from(filter)) {
+                        Ok(removed) => eprintln!("✅ Removed {} cache entries", removed.len()),
+                        Err(e) => eprintln!("❌ Failure: {:#}", e),
+                    }
+                }
+                CacheSubcommand::Prune { older_than, kind } => {
+                    let filter = CacheFilter {
+                        kinds: kind,
+                        ..Default::default()
+                    };
+                    match driver.prune_cache(&filter, chrono::Duration::days(older_than)) {
+                        Ok(removed) => eprintln!("✅ Pruned {} cache entries", removed.len()),
+                        Err(e) => eprintln!("❌ Failure: {:#}", e),
+                    }
+                }
+                CacheSubcommand::Export { file } => match driver.export_cache(&file) {
+                    Ok(_) => eprintln!("✅ Cache exported to `{}`", file.display()),
+                    Err(e) => eprintln!("❌ Failure: {:#}", e),
+                },
+                CacheSubcommand::Import { file } => match driver.import_cache(&file) {
+                    Ok(imported) => eprintln!("✅ Imported {} cache entries", imported),
+                    Err(e) => eprintln!("❌ Failure: {:#}", e),
+                },
+            }
+        }
+
+        fn handle_list_dirs_command(global_args: &GlobalArgs) {
+            let driver = get_driver(global_args, None, None);
             match driver.list_app_directories() {
                 Ok(dirs) => {
                     for (name, path) in dirs {
@@ -176,22 +841,38 @@ impl ElvCli {
             }
         }
 
-        fn handle_get_leaderboard(token_args: TokenArgs, year: Option<i32>) {
-            let driver = get_driver(Some(token_args), None);
-            match driver.get_leaderboard(year.unwrap_or_else(determine_year)) {
+        fn handle_get_leaderboard(
+            global_args: &GlobalArgs,
+            token_args: TokenArgs,
+            year: Option<i32>,
+        ) {
+            let driver = get_driver(global_args, Some(token_args), None);
+            match driver
+                .get_leaderboard(year.unwrap_or_else(|| determine_year(&driver.configuration)))
+            {
                 Ok(text) => println!("{text}"),
                 Err(e) => eprintln!("❌ Error when getting the leaderboards: {}", e.to_string()),
             }
         }
 
         fn handle_get_private_leaderboard(
+            global_args: &GlobalArgs,
             token_args: TokenArgs,
-            leaderboard_id: &str,
+            leaderboard_id: Option<String>,
             year: Option<i32>,
         ) {
-            let driver = get_driver(Some(token_args), None);
-            let year = year.unwrap_or_else(|| determine_year());
-            match driver.get_private_leaderboard(leaderboard_id, year) {
+            let driver = get_driver(global_args, Some(token_args), None);
+            let leaderboard_id = match leaderboard_id
+                .or_else(|| driver.configuration.project.leaderboards.first().cloned())
+            {
+                Some(id) => id,
+                None => {
+                    eprintln!("❌ No leaderboard ID given. Pass it with `--leaderboard-id` or set `project.leaderboards` in `elv.toml`");
+                    return;
+                }
+            };
+            let year = year.unwrap_or_else(|| determine_year(&driver.configuration));
+            match driver.get_private_leaderboard(&leaderboard_id, year) {
                 Ok(private_leaderboard) => {
                     println!("{}", private_leaderboard.cli_fmt(&driver.configuration))
                 }
@@ -199,10 +880,62 @@ impl ElvCli {
             }
         }
 
-        fn handle_get_stars(year: Option<i32>) {
-            let driver = get_driver(None, None);
-            match driver.get_stars(year.unwrap_or_else(determine_year)) {
-                Ok(stars) => println!("{}", stars),
+        fn handle_get_stars(
+            global_args: &GlobalArgs,
+            year: Option<i32>,
+            plain: bool,
+            export: Option<(ImageFormat, PathBuf)>,
+        ) {
+            let driver = get_driver(global_args, None, None);
+            if plain {
+                colored::control::set_override(false);
+            }
+            let year = year.unwrap_or_else(|| determine_year(&driver.configuration));
+            let stars = match driver.get_stars(year) {
+                Ok(stars) => stars,
+                Err(e) => {
+                    eprintln!("❌ Failure: {:#}", e);
+                    return;
+                }
+            };
+            match export {
+                Some((format, out)) => {
+                    match write_to_file(&out, &calendar_image::render(&stars, year, format), true) {
+                        Ok(()) => eprintln!("✅ Calendar written to `{}`", out.display()),
+                        Err(e) => eprintln!("❌ Failure: {:#}", e),
+                    }
+                }
+                None => println!("{}", stars.cli_fmt(&driver.configuration)),
+            }
+        }
+
+        fn handle_prefetch_command(
+            global_args: &GlobalArgs,
+            token_args: TokenArgs,
+            year: Option<i32>,
+            mut leaderboard_ids: Vec<String>,
+        ) {
+            let driver = get_driver(global_args, Some(token_args), None);
+            if leaderboard_ids.is_empty() {
+                leaderboard_ids = driver.configuration.project.leaderboards.clone();
+            }
+            let year = year.unwrap_or_else(|| determine_year(&driver.configuration));
+            match driver.prefetch(year, &leaderboard_ids) {
+                Ok(_) => eprintln!("✅ Cache filled"),
+                Err(e) => eprintln!("❌ Failure: {:#}", e),
+            }
+        }
+
+        fn handle_sync_command(
+            global_args: &GlobalArgs,
+            token_args: TokenArgs,
+            year: Option<usize>,
+        ) {
+            let driver = get_driver(global_args, Some(token_args), None);
+            let year = year.unwrap_or_else(|| determine_year(&driver.configuration) as usize);
+            match driver.sync_history(year) {
+                Ok(0) => eprintln!("✅ The history of {} is up to date", year),
+                Ok(recorded) => eprintln!("✅ Recorded {} answers from {}", recorded, year),
                 Err(e) => eprintln!("❌ Failure: {:#}", e),
             }
         }
@@ -219,8 +952,89 @@ impl ElvCli {
         fn handle_set_config(key: &str, value: String) {
             match Driver::set_config_key(key, value) {
                 Ok(_) => println!("✅ Key {key} successfully updated"),
-                Err(e) => eprintln!("❌ Failure: {}", e.to_string()),
+                Err(e) => eprintln!("❌ Failure: {:#}", e),
+            }
+        }
+
+        fn handle_config_keys() {
+            for key in CONFIGURATION_KEYS {
+                println!(
+                    "{} ({}, default: {})\n    {}",
+                    key.key,
+                    key.value_type,
+                    match key.default {
+                        Some("") => "\"\"",
+                        Some(default) => default,
+                        None => "not set",
+                    },
+                    key.description
+                );
+            }
+        }
+
+        fn handle_get_config_key(key: &str) -> ExitCode {
+            match Driver::get_config_key(key) {
+                Ok(Some(value)) => println!("{}", value),
+                Ok(None) => eprintln!("{} is not set", key),
+                Err(e) => {
+                    eprintln!("❌ {:#}", e);
+                    return ExitCode::FAILURE;
+                }
+            }
+            ExitCode::SUCCESS
+        }
+
+        fn handle_unset_config(key: &str) {
+            match Driver::unset_config_key(key) {
+                Ok(_) => println!("✅ Key {key} removed, its default value is used now"),
+                Err(e) => eprintln!("❌ Failure: {:#}", e),
+            }
+        }
+
+        /// Opens a copy of the configuration file in the editor and saves it once it is valid
+        fn handle_edit_config() {
+            let path = Configuration::file_path();
+            // Writes the default configuration file if there is none yet
+            Configuration::new();
+            let draft =
+                std::env::temp_dir().join(format!("elv-config-{}.toml", std::process::id()));
+            if let Err(e) = std::fs::copy(&path, &draft) {
+                eprintln!("❌ Failed to copy `{}`: {}", path.display(), e);
+                return;
+            }
+            let editor = std::env::var("VISUAL")
+                .or_else(|_| std::env::var("EDITOR"))
+                .unwrap_or_else(|_| if cfg!(windows) { "notepad" } else { "vi" }.to_owned());
+            loop {
+                let mut editor_args = editor.split_whitespace();
+                let program = editor_args.next().unwrap_or("vi");
+                let status = std::process::Command::new(program)
+                    .args(editor_args)
+                    .arg(&draft)
+                    .status();
+                if let Err(e) = status {
+                    eprintln!("❌ Failed to start the editor `{}`: {}", editor, e);
+                    return;
+                }
+                let contents = std::fs::read_to_string(&draft).unwrap_or_default();
+                match Configuration::replace_file_contents(&contents) {
+                    Ok(_) => {
+                        println!("✅ Configuration saved");
+                        break;
+                    }
+                    Err(e) => {
+                        eprintln!("❌ {:#}", e);
+                        eprint!("Edit the file again? [Y/n] ");
+                        let mut reply = String::new();
+                        std::io::stdin().read_line(&mut reply).ok();
+                        if reply.trim().eq_ignore_ascii_case("n") {
+                            eprintln!("The configuration is not changed");
+                            break;
+                        }
+                    }
+                }
             }
+            std::fs::remove_file(&draft).ok();
         }
 
         fn handle_token_comamand(token: Option<String>) {
@@ -234,28 +1048,54 @@ impl ElvCli {
             }
         }
 
-        fn determine_date(riddle_args: RiddleArgs) -> Result<(usize, usize)> {
-            let est_now = chrono::Utc::now() - chrono::Duration::hours(4);
+        fn determine_date(
+            configuration: &Configuration,
+            riddle_args: RiddleArgs,
+        ) -> Result<(usize, usize)> {
+            let year = riddle_args.year.or(configuration.project.year);
             let best_guess_date =
-                RiddleDate::best_guess(riddle_args.year, riddle_args.day, est_now)?;
+                RiddleDate::best_guess(year, riddle_args.day, &chrono::Utc::now())?;
             Ok((best_guess_date.year, best_guess_date.day))
         }
 
-        fn determine_year() -> i32 {
-            let est_now = chrono::Utc::now() - chrono::Duration::hours(4);
-            if est_now.month() == 12 {
-                est_now.year()
-            } else {
-                est_now.year() - 1
+        fn determine_year(configuration: &Configuration) -> i32 {
+            if let Some(year) = configuration.project.year {
+                return year as i32;
             }
+            Event::latest(&chrono::Utc::now()).year as i32
         }
 
         fn build_configuration(
+            global_args: &GlobalArgs,
             token_args: Option<TokenArgs>,
             terminal_width: Option<usize>,
         ) -> Result<Configuration, anyhow::Error> {
             let mut config_builder = Configuration::builder();
 
+            if global_args.offline {
+                config_builder = config_builder
+                    .set_override("aoc.offline", true)
+                    .context("❌ Failed to set the override on the offline mode")?;
+            }
+
+            let http_args = &global_args.http_args;
+            config_builder = config_builder
+                .set_override_option("http.proxy", http_args.proxy.clone())?
+                .set_override_option("http.no_proxy", http_args.no_proxy.clone())?
+                .set_override_option("http.connect_timeout_secs", http_args.connect_timeout)?
+                .set_override_option("http.request_timeout_secs", http_args.request_timeout)?
+                .set_override_option(
+                    "http.ca_bundle",
+                    http_args
+                        .ca_bundle
+                        .as_ref()
+                        .map(|path| path.to_string_lossy().into_owned()),
+                )?
+                .set_override_option(
+                    "http.user_agent_contact",
+                    http_args.user_agent_contact.clone(),
+                )?;
+
             if let Some(token) = token_args.and_then(|args| args.token) {
                 config_builder = config_builder
                     .set_override("aoc.token", token)
@@ -272,12 +1112,17 @@ impl ElvCli {
             config_builder
                 .build()?
                 .try_deserialize::<Configuration>()
+                .map(Configuration::in_project)
                 .or(Ok(Configuration::new()))
         }
 
-        fn get_driver(token_args: Option<TokenArgs>, terminal_width: Option<usize>) -> Driver {
+        fn get_driver(
+            global_args: &GlobalArgs,
+            token_args: Option<TokenArgs>,
+            terminal_width: Option<usize>,
+        ) -> Driver {
             Driver::new(
-                build_configuration(token_args, terminal_width)
+                build_configuration(global_args, token_args, terminal_width)
                     .expect("❌ Failed to build the configuration for the applciation"),
             )
         }
@@ -0,0 +1,132 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::infrastructure::cache_inventory::{CacheFilter, CacheKind};
+
+#[derive(Debug, Args)]
+pub struct CacheFilterArgs {
+    /// Select only the entries of this kind
+    ///
+    /// Can be passed multiple times.
+    #[arg(short, long)]
+    pub kind: Vec<CacheKind>,
+
+    /// Select only the entries for this year
+    #[arg(short, long)]
+    pub year: Option<usize>,
+
+    /// Select only the entries for this day
+    #[arg(short, long)]
+    pub day: Option<usize>,
+
+    /// Select only the private leaderboard with this ID
+    #[arg(long, visible_aliases = ["id"])]
+    pub leaderboard_id: Option<String>,
+}
+
+impl From<CacheFilterArgs> for CacheFilter {
+    fn from(args: CacheFilterArgs) -> Self {
+        CacheFilter {
+            kinds: args.kind,
+            year: args.year,
+            day: args.day,
+            leaderboard_id: args.leaderboard_id,
+        }
+    }
+}
+
+#[derive(clap::Parser, Debug)]
+pub enum CacheSubcommand {
+    /// List the cached inputs, descriptions, submission histories and pages
+    ///
+    /// Examples:
+    /// elv cache list
+    /// elv cache list -y 2022 -k input -k description
+    #[command(verbatim_doc_comment, visible_aliases = ["l", "ls"])]
+    List {
+        #[command(flatten)]
+        filter: CacheFilterArgs,
+    },
+
+    /// Print a single cache entry
+    ///
+    /// Examples:
+    /// elv cache show input -y 2022 -d 1
+    /// elv cache show submissions -y 2022 -d 1
+    /// elv cache show private-leaderboard -y 2022 --id 123456
+    #[command(verbatim_doc_comment)]
+    Show {
+        /// The kind of the entry
+        kind: CacheKind,
+
+        /// The year of the entry
+        #[arg(short, long)]
+        year: usize,
+
+        /// The day of the entry, if the entry is specific to a day
+        #[arg(short, long)]
+        day: Option<usize>,
+
+        /// The ID of the private leaderboard
+        #[arg(long, visible_aliases = ["id"])]
+        leaderboard_id: Option<String>,
+    },
+
+    /// Remove the selected entries from the cache
+    ///
+    /// Examples:
+    /// elv cache rm -y 2022 -d 1
+    /// elv cache rm -k submissions -y 2021
+    /// elv cache rm --all
+    #[command(verbatim_doc_comment, visible_aliases = ["remove"])]
+    #[command(group(
+        clap::ArgGroup::new("selection")
+            .required(true)
+            .multiple(true)
+            .args(["kind", "year", "day", "leaderboard_id", "all"])
+    ))]
+    Rm {
+        #[command(flatten)]
+        filter: CacheFilterArgs,
+
+        /// Remove every entry, including the submission histories
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Remove the cached pages older than a number of days
+    ///
+    /// By default, only the inputs and the private leaderboards are pruned.
+    /// The descriptions hold the answers you have given, the stars pages your
+    /// calendars and the submission histories your memory of the past
+    /// submissions, so they are pruned only if you ask for them with `--kind`,
+    /// e.g. `--kind description`.
+    Prune {
+        /// Remove the entries older than this number of days
+        #[arg(long, default_value_t = 30)]
+        older_than: i64,
+
+        /// Prune only the entries of this kind
+        ///
+        /// Can be passed multiple times.
+        #[arg(short, long, default_values_t = [CacheKind::Input, CacheKind::PrivateLeaderboard])]
+        kind: Vec<CacheKind>,
+    },
+
+    /// Export the whole cache to a gzipped tarball
+    ///
+    /// Use it together with `elv cache import` to move the cache between machines.
+    Export {
+        /// The path of the created archive
+        file: PathBuf,
+    },
+
+    /// Import the cache from a tarball created by `elv cache export`
+    ///
+    /// Entries already in the cache are overwritten by the ones from the archive.
+    Import {
+        /// The path of the archive
+        file: PathBuf,
+    },
+}
@@ -2,9 +2,12 @@ use std::path::PathBuf;
 
 use clap::Args;
 
-use crate::domain::riddle_part::RiddlePart;
+use crate::domain::{
+    day_range::DayRange, description_parts::DescriptionParts, riddle_part::RiddlePart,
+};
+use crate::infrastructure::calendar_image::ImageFormat;
 
-use super::cli_config_subcommand::ConfigSubcommand;
+use super::{cli_cache_subcommand::CacheSubcommand, cli_config_subcommand::ConfigSubcommand};
 
 #[derive(Debug, Args)]
 pub struct RiddleArgs {
@@ -25,6 +28,57 @@ pub struct RiddleArgs {
     pub day: Option<usize>,
 }
 
+#[derive(Debug, Args)]
+pub struct RiddleRangeArgs {
+    /// The year of the challenge
+    ///
+    /// If you do not supply a year and a day, the current year will be used.
+    /// If you do not supply a year, but supply a day, the previous year
+    /// will be used.
+    #[arg(short, long, value_parser = clap::value_parser!(usize))]
+    pub year: Option<usize>,
+
+    /// The day of the challenge or a range of days
+    ///
+    /// If you do not supply a day, the current day of the month will be used
+    /// (if the current month is December). If the current month is not December,
+    /// the application will not be able to guess the day.
+    /// Pass a range like `1..=25` to get many riddles at once.
+    #[arg(short, long, conflicts_with = "all")]
+    pub day: Option<DayRange>,
+
+    /// Get all the released riddles of the year
+    #[arg(long, default_value = "false")]
+    pub all: bool,
+}
+
+impl RiddleRangeArgs {
+    /// Returns the arguments of a single riddle, unless many days were requested
+    pub fn single_riddle(&self) -> Option<RiddleArgs> {
+        if self.all {
+            return None;
+        }
+        match self.day {
+            None => Some(RiddleArgs {
+                year: self.year,
+                day: None,
+            }),
+            Some(range) => range.single_day().map(|day| RiddleArgs {
+                year: self.year,
+                day: Some(day),
+            }),
+        }
+    }
+
+    /// Returns the requested range of days, or `None` for all the days of the event
+    pub fn day_range(&self) -> Option<DayRange> {
+        match self.day {
+            Some(range) if !self.all => Some(range),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Args)]
 pub struct TokenArgs {
     /// Your Advent of Code session token
@@ -36,26 +90,138 @@ pub struct TokenArgs {
     pub token: Option<String>,
 }
 
+#[derive(Debug, Args)]
+pub struct GlobalArgs {
+    /// Work without connecting to the Advent of Code servers
+    ///
+    /// In the offline mode, the inputs, descriptions, stars and private
+    /// leaderboards are served only from the cache. Use `elv prefetch`
+    /// beforehand to fill the cache. You can also turn the offline mode on
+    /// permanently with `elv config set aoc.offline true`.
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    #[command(flatten)]
+    pub http_args: HttpArgs,
+}
+
+/// Overrides of the `[http]` section of the configuration
+#[derive(Debug, Args)]
+#[command(next_help_heading = "HTTP")]
+pub struct HttpArgs {
+    /// The URL of the proxy used to connect to the Advent of Code servers
+    #[arg(long, global = true, value_name = "URL")]
+    pub proxy: Option<String>,
+
+    /// The comma-separated list of hosts that bypass the proxy
+    #[arg(long, global = true, value_name = "HOSTS")]
+    pub no_proxy: Option<String>,
+
+    /// How long to wait for the connection to the servers, in seconds
+    #[arg(long, global = true, value_name = "SECONDS")]
+    pub connect_timeout: Option<u64>,
+
+    /// The longest time a whole request may take, in seconds
+    #[arg(long, global = true, value_name = "SECONDS", alias = "read-timeout")]
+    pub request_timeout: Option<u64>,
+
+    /// The PEM file with extra trusted root certificates
+    #[arg(long, global = true, value_name = "PATH")]
+    pub ca_bundle: Option<PathBuf>,
+
+    /// The contact appended to the user agent of the requests
+    #[arg(long, global = true, value_name = "CONTACT")]
+    pub user_agent_contact: Option<String>,
+}
+
+/// The options deciding which parts of a description are shown and how
+#[derive(Debug, Args)]
+pub struct DescriptionArgs {
+    /// The column width of the output in characters
+    ///
+    /// Some terminals have limited horizontal space, so this option
+    /// can be used to limit the width of the output.
+    /// Defaults to the `cli.output_width` configuration value.
+    #[arg(short, long)]
+    pub width: Option<usize>,
+
+    /// Render the description with colours, boxed code blocks and links as footnotes
+    ///
+    /// Set `cli.rich_descriptions` to `true` in the configuration to always
+    /// render the descriptions this way. The colours are turned off when
+    /// the `NO_COLOR` environment variable is set or the description is
+    /// written to a file.
+    #[arg(long)]
+    pub rich: bool,
+
+    /// Show the hidden jokes in the titles of the riddle text (implies `--rich`)
+    #[arg(long)]
+    pub easter_eggs: bool,
+
+    /// Show only this part of the riddle
+    #[arg(short, long, value_enum)]
+    pub part: Option<RiddlePart>,
+
+    /// Show only the part of the riddle you have not seen yet
+    ///
+    /// This is the first part of an unsolved riddle and the second part
+    /// after solving the first one.
+    #[arg(long, conflicts_with = "part")]
+    pub new: bool,
+
+    /// Print the original HTML of the riddle instead of formatting it
+    #[arg(long, conflicts_with_all = ["rich", "easter_eggs"])]
+    pub raw: bool,
+
+    /// Download the description again instead of using the cached one
+    ///
+    /// The descriptions are cached until you solve a part of the riddle with `elv`.
+    /// Use this if you solved it on the website.
+    #[arg(long)]
+    pub refresh: bool,
+}
+
+impl DescriptionArgs {
+    pub fn parts(&self) -> DescriptionParts {
+        match (&self.part, self.new) {
+            (Some(part), _) => DescriptionParts::Only(part.clone()),
+            (None, true) => DescriptionParts::New,
+            (None, false) => DescriptionParts::All,
+        }
+    }
+}
+
 #[derive(Debug, clap::Subcommand)]
 pub enum CliCommand {
     /// 📄 Get the description of the challenge
     ///
     /// This command will download the description of the challenge and write it to
-    /// the console.
+    /// the console. If you ask for many days with `--all` or a range of days,
+    /// the descriptions are written to files instead.
     #[command(visible_aliases = ["desc", "d"])]
     Description {
         #[command(flatten)]
-        riddle_args: RiddleArgs,
+        riddle_args: RiddleRangeArgs,
 
         #[command(flatten)]
         token: TokenArgs,
 
-        /// The column width of the output in characters
+        #[command(flatten)]
+        description_args: DescriptionArgs,
+
+        /// The description will be written to the file with this name
         ///
-        /// Some terminals have limited horizontal space, so this option
-        /// can be used to limit the width of the output.
-        #[arg(short, long, default_value_t = 120)]
-        width: usize,
+        /// This is a template of the path. The placeholders `{year}`, `{day}`,
+        /// `{day:02}`, `{part}` and `{title}` are replaced with the year, the day,
+        /// the zero-padded day, the selected part and the title of the riddle.
+        /// Defaults to `project.description` or `descriptions/{year}/day{day:02}.txt`
+        /// for many descriptions.
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+
+        /// Replaces the existing files even if their contents differ
+        #[arg(long)]
+        overwrite: bool,
     },
 
     /// 📨 Get the input for the challenge
@@ -65,17 +231,31 @@ pub enum CliCommand {
     /// been downloaded, it will be printed from the cache. If the input
     /// has not been downloaded, it will be downloaded and then printed.
     /// The input will be cached in the application's cache directory.
+    ///
+    /// Pass `--all` or a range of days (e.g. `--day 1..=25`) to download
+    /// the inputs of many riddles at once. The inputs are then written to
+    /// `inputs/{year}/day{day:02}.txt` by default.
     #[command(visible_aliases = ["i"])]
     Input {
         #[command(flatten)]
-        riddle_args: RiddleArgs,
+        riddle_args: RiddleRangeArgs,
 
         #[command(flatten)]
         token_args: TokenArgs,
 
         /// The input will be written to the file with this name
-        #[arg(short, long, default_value = "input", conflicts_with = "no_file")]
-        out: PathBuf,
+        ///
+        /// This is a template of the path. The placeholders `{year}`, `{day}`,
+        /// `{day:02}` and `{title}` are replaced with the year, the day,
+        /// the zero-padded day and the title of the riddle. Defaults to
+        /// `project.input`, or to "input" for a single riddle and
+        /// `inputs/{year}/day{day:02}.txt` for many riddles.
+        #[arg(short, long, conflicts_with = "no_file")]
+        out: Option<PathBuf>,
+
+        /// Replaces the existing files even if their contents differ
+        #[arg(long, conflicts_with = "no_file")]
+        overwrite: bool,
 
         /// Suppresses writing to the file
         #[arg(short, long, default_value = "false", conflicts_with = "out")]
@@ -97,14 +277,55 @@ pub enum CliCommand {
     Submit {
         /// The answer to the challenge
         ///
-        /// Your answer to the challenge. This argument is required.
-        answer: String,
+        /// Your answer to the challenge. Pass `-` to read it from the standard input.
+        #[arg(
+            allow_negative_numbers = true,
+            required_unless_present_any = ["answer_file", "from_output"]
+        )]
+        answer: Option<String>,
 
         /// The part of the challenge
         ///
         /// Possible values: "one", "two".
         part: Option<RiddlePart>,
 
+        /// Read the answer from this file
+        ///
+        /// The part of the riddle can still be given after the options,
+        /// e.g. `elv submit --answer-file answer.txt two`.
+        #[arg(long, value_name = "PATH")]
+        answer_file: Option<PathBuf>,
+
+        /// Take the last non-empty line of the output piped into `elv` as the answer
+        ///
+        /// Lets you pipe the whole output of your solution into `elv`,
+        /// e.g. `cargo run | elv submit --from-output`. Also works with `--answer-file`.
+        #[arg(long)]
+        from_output: bool,
+
+        /// Submit the answer even if it looks like a mistake
+        ///
+        /// `elv` holds back the answers that are negative numbers, look like
+        /// debug output or repeat the answer to the first part.
+        #[arg(short, long)]
+        force: bool,
+
+        /// Show what would happen without submitting the answer
+        ///
+        /// Prints the riddle and the part the answer is meant for, whether
+        /// the history of your submissions settles it without asking the servers,
+        /// and the request that would be sent.
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Wait until you are allowed to submit again instead of giving up
+        ///
+        /// After a wrong answer, Advent of Code makes you wait before the next one.
+        /// With this option `elv` counts down the remaining time and submits
+        /// the answer as soon as the wait is over.
+        #[arg(long, conflicts_with = "dry_run")]
+        wait: bool,
+
         #[command(flatten)]
         riddle_args: RiddleArgs,
 
@@ -139,9 +360,10 @@ pub enum CliCommand {
         /// The ID of the leaderboard
         ///
         /// The ID of the leaderboard is the part of the last part of the URL of
-        /// the leaderboard you want to visit.
+        /// the leaderboard you want to visit. If you do not supply an ID, the first
+        /// of `project.leaderboards` will be used.
         #[arg(short, long, visible_aliases = ["id"])]
-        leaderboard_id: String,
+        leaderboard_id: Option<String>,
 
         /// The year of the challenge
         ///
@@ -161,15 +383,166 @@ pub enum CliCommand {
         /// If you do not supply a year, this command will pull the leaderboards from
         /// the latest event.
         year: Option<i32>,
+
+        /// Print the calendar without the colours of the riddle page
+        ///
+        /// The colours are also turned off when the `NO_COLOR` environment
+        /// variable is set.
+        #[arg(long)]
+        plain: bool,
+
+        /// Export the calendar as a standalone image instead of printing it
+        ///
+        /// The image shows the ASCII art in its colours and the stars you
+        /// have collected. It is written to the file given with `--out`.
+        #[arg(long, value_name = "FORMAT", requires = "out")]
+        export: Option<ImageFormat>,
+
+        /// The file the exported image is written to
+        ///
+        /// The file is replaced every time the calendar is exported.
+        #[arg(short, long, requires = "export")]
+        out: Option<PathBuf>,
+    },
+
+    /// 📦 Download a whole event into the cache
+    ///
+    /// This command downloads the inputs and the descriptions of all the released
+    /// riddles of a year, the stars page and, optionally, private leaderboards.
+    /// Inputs and descriptions that are already cached are not downloaded again.
+    /// Use it before going offline, so `elv --offline` can serve them later.
+    Prefetch {
+        #[command(flatten)]
+        token_args: TokenArgs,
+
+        /// The year of the challenge
+        ///
+        /// If you do not supply a year, this command will download the latest event.
+        #[arg(short, long, value_parser = clap::value_parser!(i32))]
+        year: Option<i32>,
+
+        /// The ID of a private leaderboard to download as well
+        ///
+        /// Can be passed multiple times. Defaults to `project.leaderboards`.
+        #[arg(short, long, visible_aliases = ["id"])]
+        leaderboard_id: Vec<String>,
+    },
+
+    /// 🔄 Record the answers to the riddles you solved elsewhere
+    ///
+    /// Reads the answers shown on the pages of your solved riddles and adds
+    /// them to the history of your submissions. Afterwards `elv` knows which
+    /// parts are solved, even if you solved them on the website or another
+    /// machine, and does not submit their answers again.
+    Sync {
+        #[command(flatten)]
+        token_args: TokenArgs,
+
+        /// The year of the event
+        ///
+        /// If you do not supply a year, this command will sync the latest event.
+        #[arg(short, long)]
+        year: Option<usize>,
+    },
+
+    /// 🧪 Run your solution on the examples from the riddle description
+    ///
+    /// Finds the example inputs and their answers in the description of
+    /// the riddle, runs your solution on them and compares the answers.
+    /// The solution is the shell command set in `solutions.command`. It gets
+    /// the input on its standard input and the last line it prints is its answer.
+    /// Add your own examples or fix the ones picked from the description
+    /// in the examples file (`examples/{year}/day{day:02}.toml` by default).
+    #[command(verbatim_doc_comment)]
+    Test {
+        #[command(flatten)]
+        riddle_args: RiddleArgs,
+
+        #[command(flatten)]
+        token_args: TokenArgs,
+
+        /// Test only this part of the riddle
+        #[arg(short, long, value_enum)]
+        part: Option<RiddlePart>,
+
+        /// The command running the solution, instead of `solutions.command`
+        ///
+        /// The placeholders `{year}`, `{day}`, `{day:02}` and `{part}`
+        /// are replaced with the date and the part of the riddle.
+        #[arg(short, long)]
+        command: Option<String>,
+    },
+
+    /// ✔️  Check your solutions against the answers known to be correct
+    ///
+    /// Runs the solution of every released riddle with a known answer on its
+    /// input and compares the results with the answers. The answers come from
+    /// the history of your submissions (see `elv sync`) and the answers file
+    /// (`answers.toml` by default). Nothing is submitted.
+    /// Use it to find the days broken by changes to the shared code.
+    #[command(verbatim_doc_comment)]
+    Verify {
+        #[command(flatten)]
+        token_args: TokenArgs,
+
+        /// The year of the event
+        ///
+        /// If you do not supply a year, this command will check the latest event.
+        #[arg(short, long)]
+        year: Option<usize>,
+
+        /// The day or the range of days to check, e.g. `1..=10`
+        ///
+        /// Checks all the released days if not given.
+        #[arg(short, long)]
+        day: Option<DayRange>,
+
+        /// The command running the solution, instead of `solutions.command`
+        ///
+        /// The placeholders `{year}`, `{day}`, `{day:02}` and `{part}`
+        /// are replaced with the date and the part of the riddle.
+        #[arg(short, long)]
+        command: Option<String>,
+    },
+
+    /// 🖥️  Open the dashboard of a riddle
+    ///
+    /// Shows the description, the input, your submissions, the countdown
+    /// to the unlock and a private leaderboard in a full-screen terminal UI.
+    /// Press `q` to quit. The keybindings are listed at the bottom of the screen.
+    #[command(verbatim_doc_comment)]
+    Tui {
+        #[command(flatten)]
+        riddle_args: RiddleArgs,
+
+        #[command(flatten)]
+        token_args: TokenArgs,
+
+        /// The ID of the private leaderboard shown in the dashboard
+        ///
+        /// Defaults to the first of `project.leaderboards`.
+        #[arg(short, long, visible_aliases = ["id"])]
+        leaderboard_id: Option<String>,
     },
 
     /// 🗑️  Clear the cache
     ///
-    /// This command will clear the cache of the application. The cache is used
-    /// to store the input and the results of submissions. This command will
-    /// delete the cache directories and all of their contents.
+    /// This command will clear the cached inputs, descriptions, stars pages
+    /// and private leaderboards. The history of your submissions is kept.
+    /// See `elv cache --help` to inspect the cache and remove the entries
+    /// selectively.
     ClearCache,
 
+    /// 🗄️  Inspect and manage the cache
+    ///
+    /// The cache stores the inputs, the descriptions, the histories of your
+    /// submissions, the stars pages and the private leaderboards.
+    #[command(visible_aliases = ["c"])]
+    Cache {
+        #[clap(subcommand)]
+        cmd: CacheSubcommand,
+    },
+
     /// 📁 List the application directories
     ///
     /// Lists the directories used by the application. This command will print
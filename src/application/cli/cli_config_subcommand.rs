@@ -4,13 +4,27 @@ pub enum ConfigSubcommand {
     #[command(visible_aliases = ["l"])]
     List {},
 
+    /// List all the supported configuration keys with their types and defaults
+    #[command(visible_aliases = ["k"])]
+    Keys {},
+
+    /// Print the value of the specified configuration key
+    ///
+    /// The value comes from the configuration file, `elv.toml` or the environment,
+    /// whichever takes precedence.
+    Get {
+        /// The configuration key, e.g. `cli.output_width`
+        key: String,
+    },
+
     /// Update the value of the specified configuration key
     ///
     /// Examples:
     /// elv config set aoc.token abscdft123145
     /// elv config set cli.output_width 150
+    /// elv config set project.leaderboards 123456,654321
     ///
-    /// See `elv config list` for all available configuration keys.
+    /// See `elv config keys` for all available configuration keys.
     #[command(verbatim_doc_comment)]
     Set {
         /// The updated configuration key
@@ -19,4 +33,16 @@ pub enum ConfigSubcommand {
         /// The value used to update the key
         value: String,
     },
+
+    /// Remove the specified key from the configuration file, restoring its default
+    Unset {
+        /// The configuration key, e.g. `http.proxy`
+        key: String,
+    },
+
+    /// Open the configuration file in your editor
+    ///
+    /// The editor is taken from the `VISUAL` or `EDITOR` environment variables.
+    /// The file is checked when you close the editor and saved only if it is valid.
+    Edit {},
 }
@@ -1,4 +1,4 @@
-use super::cli_command::CliCommand;
+use super::cli_command::{CliCommand, GlobalArgs};
 
 /// 🎄 Your Advent of Code CLI 🎄
 ///
@@ -16,6 +16,9 @@ use super::cli_command::CliCommand;
 pub struct CliInterface {
     #[command(subcommand)]
     pub command: CliCommand,
+
+    #[command(flatten)]
+    pub global_args: GlobalArgs,
 }
 
 #[cfg(test)]
@@ -27,4 +30,21 @@ mod tests {
         use clap::CommandFactory;
         CliInterface::command().debug_assert()
     }
+
+    #[test]
+    fn prunes_only_the_inputs_and_the_leaderboards_by_default() {
+        use crate::application::cli::cli_cache_subcommand::CacheSubcommand;
+        use crate::infrastructure::cache_inventory::CacheKind;
+        use clap::Parser;
+
+        let cli = CliInterface::parse_from(["elv", "cache", "prune"]);
+
+        let CliCommand::Cache {
+            cmd: CacheSubcommand::Prune { kind, .. },
+        } = cli.command
+        else {
+            panic!("Expected the cache prune command");
+        };
+        assert_eq!(kind, [CacheKind::Input, CacheKind::PrivateLeaderboard]);
+    }
 }
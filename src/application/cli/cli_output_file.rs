@@ -0,0 +1,76 @@
+use anyhow::{Context, Result};
+
+/// Writes the file, unless it already exists with different contents
+/// and `overwrite` is not set
+pub fn write_to_file(out: &std::path::Path, contents: &str, overwrite: bool) -> Result<()> {
+    if !overwrite {
+        if let Ok(existing) = std::fs::read_to_string(out) {
+            if existing != contents {
+                anyhow::bail!(
+                    "The file `{}` already exists with different contents. Pass `--overwrite` to replace it",
+                    out.display()
+                );
+            }
+        }
+    }
+    if let Some(parent) = out.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create the directory {}", parent.display()))?;
+    }
+    std::fs::write(out, contents)
+        .with_context(|| format!("Failed to write the file `{}`", out.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_file(name: &str) -> std::path::PathBuf {
+        let file = std::env::temp_dir()
+            .join(format!("elv-output-file-test-{}", std::process::id()))
+            .join(name);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, "old contents").unwrap();
+        file
+    }
+
+    #[test]
+    fn refuses_to_overwrite_different_contents() {
+        let file = test_file("different");
+
+        let result = write_to_file(&file, "new contents", false);
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("already exists with different contents"));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "old contents");
+    }
+
+    #[test]
+    fn overwrites_different_contents_when_asked() {
+        let file = test_file("overwrite");
+
+        write_to_file(&file, "new contents", true).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "new contents");
+    }
+
+    #[test]
+    fn accepts_identical_contents() {
+        let file = test_file("identical");
+
+        write_to_file(&file, "old contents", false).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "old contents");
+    }
+
+    #[test]
+    fn creates_the_missing_directories() {
+        let file = test_file("missing").with_file_name("nested").join("file");
+
+        write_to_file(&file, "new contents", false).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "new contents");
+    }
+}
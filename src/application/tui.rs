@@ -0,0 +1,127 @@
+mod app;
+mod ui;
+
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{self, Event, KeyEventKind},
+    DefaultTerminal,
+};
+
+use self::app::{Action, App};
+use crate::{
+    domain::{description_parts::DescriptionParts, submission_outcome::SubmissionOutcome},
+    Driver,
+};
+
+/// How often the dashboard is redrawn to keep the countdown running
+const TICK: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Runs the full-screen dashboard of a riddle until the user quits
+pub fn run(driver: &Driver, year: usize, day: usize, leaderboard_id: Option<String>) -> Result<()> {
+    let mut app = App::new(year, day, leaderboard_id);
+    let mut terminal = ratatui::init();
+    let result = run_app(&mut terminal, driver, &mut app);
+    ratatui::restore();
+    result
+}
+
+fn run_app(terminal: &mut DefaultTerminal, driver: &Driver, app: &mut App) -> Result<()> {
+    perform(driver, app, Action::LoadDay);
+    if app.leaderboard_id.is_some() {
+        perform(driver, app, Action::RefreshLeaderboard);
+    }
+    // The requests may have written their progress to the terminal
+    terminal.clear()?;
+
+    loop {
+        terminal.draw(|frame| ui::draw(frame, app, &chrono::Utc::now()))?;
+        if !event::poll(TICK)? {
+            continue;
+        }
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            Event::Resize(_, _) => {
+                terminal.clear()?;
+                continue;
+            }
+            _ => continue,
+        };
+        match app.handle_key(key) {
+            Some(Action::Quit) => return Ok(()),
+            Some(action) => {
+                app.status = "⏳ Working...".to_owned();
+                terminal.draw(|frame| ui::draw(frame, app, &chrono::Utc::now()))?;
+                perform(driver, app, action);
+                terminal.clear()?;
+            }
+            None => {}
+        }
+    }
+}
+
+/// Carries out the action with the driver and stores its results in the app
+fn perform(driver: &Driver, app: &mut App, action: Action) {
+    let (year, day) = (app.year, app.day);
+    let result = match action {
+        Action::LoadDay => load_day(driver, app),
+        Action::FetchInput => driver.input(year, day).map(|input| {
+            app.input = Some(input);
+            "✅ Input downloaded".to_owned()
+        }),
+        Action::ReloadDescription => driver
+            .refresh_description(year, day)
+            .and_then(|_| driver.get_description(year, day, &DescriptionParts::All))
+            .map(|description| {
+                app.description = Some(description);
+                "✅ Description downloaded".to_owned()
+            }),
+        Action::Submit { part, answer } => {
+            let force = app.suspicious_answer.as_ref() == Some(&(part.clone(), answer.clone()));
+            app.suspicious_answer = None;
+            driver
+                .submit_answer(year, day, part.clone(), answer.clone(), force)
+                .map(|outcome| {
+                    if let Ok(history) = driver.submission_history(year, day) {
+                        app.submissions = history.submissions().to_vec();
+                    }
+                    let status = outcome.to_string().replace('\n', " ");
+                    if let SubmissionOutcome::Suspicious(_) = outcome {
+                        app.suspicious_answer = Some((part, answer));
+                        return format!("⚠️  {} Submit it again to send it anyway.", status);
+                    }
+                    status
+                })
+        }
+        Action::RefreshLeaderboard => match app.leaderboard_id.clone() {
+            Some(id) => driver
+                .get_private_leaderboard(&id, year as i32)
+                .map(|leaderboard| {
+                    app.leaderboard = Some(leaderboard);
+                    "✅ Leaderboard downloaded".to_owned()
+                }),
+            None => Ok("No leaderboard ID given".to_owned()),
+        },
+        Action::Quit => Ok(String::new()),
+    };
+    app.status = match result {
+        Ok(status) => status,
+        Err(e) => format!("❌ {:#}", e),
+    };
+}
+
+fn load_day(driver: &Driver, app: &mut App) -> Result<String> {
+    let (year, day) = (app.year, app.day);
+    app.clear_day();
+    let release_time = driver.release_time(year, day)?;
+    app.release_time = Some(release_time);
+    app.input = driver.cached_input(year, day);
+    app.submissions = driver
+        .submission_history(year, day)
+        .map(|history| history.submissions().to_vec())
+        .unwrap_or_default();
+    if chrono::Utc::now() < release_time {
+        return Ok(format!("{}-{:02} is not released yet", year, day));
+    }
+    app.description = Some(driver.get_description(year, day, &DescriptionParts::All)?);
+    Ok(format!("✅ Loaded {}-{:02}", year, day))
+}
@@ -0,0 +1,251 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::domain::{
+    event::{Event, FIRST_YEAR},
+    private_leaderboard::PrivateLeaderboard,
+    riddle_part::RiddlePart,
+    submission_result::SubmissionResult,
+    submission_status::SubmissionStatus,
+};
+
+const PAGE: u16 = 10;
+
+/// The operations on the Advent of Code data requested with the keybindings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    LoadDay,
+    FetchInput,
+    ReloadDescription,
+    Submit { part: RiddlePart, answer: String },
+    RefreshLeaderboard,
+    Quit,
+}
+
+/// The answer being typed in by the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerPrompt {
+    pub part: RiddlePart,
+    pub answer: String,
+}
+
+/// The state of the dashboard
+#[derive(Debug)]
+pub struct App {
+    pub year: usize,
+    pub day: usize,
+    pub leaderboard_id: Option<String>,
+    pub release_time: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub description: Option<String>,
+    pub description_scroll: u16,
+    pub input: Option<String>,
+    pub submissions: Vec<SubmissionResult>,
+    pub leaderboard: Option<PrivateLeaderboard>,
+    pub prompt: Option<AnswerPrompt>,
+    /// The answer held back because it looked like a mistake, sent when submitted again
+    pub suspicious_answer: Option<(RiddlePart, String)>,
+    pub status: String,
+}
+
+impl App {
+    pub fn new(year: usize, day: usize, leaderboard_id: Option<String>) -> Self {
+        Self {
+            year,
+            day,
+            leaderboard_id,
+            release_time: None,
+            description: None,
+            description_scroll: 0,
+            input: None,
+            submissions: Vec::new(),
+            leaderboard: None,
+            prompt: None,
+            suspicious_answer: None,
+            status: String::new(),
+        }
+    }
+
+    /// Forgets everything known about the previously shown riddle
+    pub fn clear_day(&mut self) {
+        self.release_time = None;
+        self.description = None;
+        self.description_scroll = 0;
+        self.input = None;
+        self.submissions.clear();
+        self.prompt = None;
+        self.suspicious_answer = None;
+    }
+
+    /// The part to submit an answer for - the first one not solved yet
+    pub fn unsolved_part(&self) -> RiddlePart {
+        let is_part_one_solved = self.submissions.iter().any(|result| {
+            result.submission.part == RiddlePart::One && result.status == SubmissionStatus::Correct
+        });
+        if is_part_one_solved {
+            RiddlePart::Two
+        } else {
+            RiddlePart::One
+        }
+    }
+
+    fn event(&self) -> Event {
+        Event { year: self.year }
+    }
+
+    /// Switches to the event of the year, keeping the day within the event
+    fn change_year(&mut self, year: usize) {
+        self.year = year;
+        self.day = self.day.min(self.event().days());
+        self.leaderboard = None;
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Action::Quit);
+        }
+        if self.prompt.is_some() {
+            return self.handle_prompt_key(key);
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => Some(Action::Quit),
+            KeyCode::Left | KeyCode::Char('h') if self.day > 1 => {
+                self.day -= 1;
+                Some(Action::LoadDay)
+            }
+            KeyCode::Right | KeyCode::Char('l') if self.day < self.event().days() => {
+                self.day += 1;
+                Some(Action::LoadDay)
+            }
+            KeyCode::Char('[') if self.year > FIRST_YEAR => {
+                self.change_year(self.year - 1);
+                Some(Action::LoadDay)
+            }
+            KeyCode::Char(']') => {
+                self.change_year(self.year + 1);
+                Some(Action::LoadDay)
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.description_scroll = self.description_scroll.saturating_sub(1);
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.description_scroll = self.description_scroll.saturating_add(1);
+                None
+            }
+            KeyCode::PageUp => {
+                self.description_scroll = self.description_scroll.saturating_sub(PAGE);
+                None
+            }
+            KeyCode::PageDown => {
+                self.description_scroll = self.description_scroll.saturating_add(PAGE);
+                None
+            }
+            KeyCode::Char('i') => Some(Action::FetchInput),
+            KeyCode::Char('d') => Some(Action::ReloadDescription),
+            KeyCode::Char('r') => Some(Action::RefreshLeaderboard),
+            KeyCode::Char('s') => {
+                self.prompt = Some(AnswerPrompt {
+                    part: self.unsolved_part(),
+                    answer: String::new(),
+                });
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_prompt_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let prompt = self.prompt.as_mut()?;
+        match key.code {
+            KeyCode::Esc => {
+                self.prompt = None;
+                None
+            }
+            KeyCode::Enter if !prompt.answer.trim().is_empty() => {
+                let prompt = self.prompt.take()?;
+                Some(Action::Submit {
+                    part: prompt.part,
+                    answer: prompt.answer.trim().to_owned(),
+                })
+            }
+            KeyCode::Tab => {
+                prompt.part = match prompt.part {
+                    RiddlePart::One => RiddlePart::Two,
+                    RiddlePart::Two => RiddlePart::One,
+                };
+                None
+            }
+            KeyCode::Backspace => {
+                prompt.answer.pop();
+                None
+            }
+            KeyCode::Char(c) => {
+                prompt.answer.push(c);
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::submission::Submission;
+
+    use super::*;
+
+    fn press(app: &mut App, code: KeyCode) -> Option<Action> {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn switches_days_within_the_event() {
+        let mut app = App::new(2022, 1, None);
+        assert_eq!(press(&mut app, KeyCode::Left), None);
+        assert_eq!(press(&mut app, KeyCode::Right), Some(Action::LoadDay));
+        assert_eq!(app.day, 2);
+
+        let mut app = App::new(2015, 25, None);
+        assert_eq!(press(&mut app, KeyCode::Char('l')), None);
+        assert_eq!(press(&mut app, KeyCode::Char('[')), None);
+        assert_eq!(press(&mut app, KeyCode::Char(']')), Some(Action::LoadDay));
+        assert_eq!(app.year, 2016);
+
+        let mut app = App::new(2024, 20, None);
+        assert_eq!(press(&mut app, KeyCode::Char(']')), Some(Action::LoadDay));
+        assert_eq!((app.year, app.day), (2025, 12));
+        assert_eq!(press(&mut app, KeyCode::Char('l')), None);
+    }
+
+    #[test]
+    fn submits_the_typed_answer() {
+        let mut app = App::new(2022, 1, None);
+        assert_eq!(press(&mut app, KeyCode::Char('s')), None);
+        for c in "12x".chars() {
+            assert_eq!(press(&mut app, KeyCode::Char(c)), None);
+        }
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(
+            press(&mut app, KeyCode::Enter),
+            Some(Action::Submit {
+                part: RiddlePart::Two,
+                answer: "12".to_owned()
+            })
+        );
+        assert!(app.prompt.is_none());
+    }
+
+    #[test]
+    fn prompts_for_the_unsolved_part() {
+        let mut app = App::new(2022, 1, None);
+        app.submissions.push(SubmissionResult::new(
+            Submission::new(RiddlePart::One, "1".to_owned(), 2022, 1),
+            SubmissionStatus::Correct,
+            "That's the right answer!".to_owned(),
+            chrono::Utc::now(),
+            std::time::Duration::ZERO,
+        ));
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.prompt.as_ref().map(|p| &p.part), Some(&RiddlePart::Two));
+    }
+}
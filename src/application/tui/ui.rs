@@ -0,0 +1,188 @@
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::app::App;
+use crate::domain::{
+    duration_string::DurationString, riddle_part::RiddlePart, solved_parts::SolvedParts,
+    submission_status::SubmissionStatus,
+};
+
+const HELP: &str =
+    "←/→ day  [/] year  ↑/↓ scroll  i input  d description  s submit  r leaderboard  q quit";
+
+pub fn draw(frame: &mut Frame, app: &App, now: &chrono::DateTime<chrono::Utc>) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(5),
+        Constraint::Length(4),
+    ])
+    .areas(frame.area());
+    let [description, side] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(body);
+    let [input, submissions, leaderboard] = Layout::vertical([
+        Constraint::Percentage(30),
+        Constraint::Percentage(30),
+        Constraint::Percentage(40),
+    ])
+    .areas(side);
+
+    frame.render_widget(header_widget(app, now), header);
+    frame.render_widget(description_widget(app), description);
+    frame.render_widget(input_widget(app, input.height), input);
+    frame.render_widget(submissions_widget(app), submissions);
+    frame.render_widget(leaderboard_widget(app), leaderboard);
+    frame.render_widget(footer_widget(app), footer);
+}
+
+fn titled_block(title: impl Into<String>) -> Block<'static> {
+    Block::default().borders(Borders::ALL).title(Span::styled(
+        title.into(),
+        Style::default().fg(Color::Yellow),
+    ))
+}
+
+fn header_widget<'a>(app: &App, now: &chrono::DateTime<chrono::Utc>) -> Paragraph<'a> {
+    let countdown = match app.release_time {
+        Some(release_time) if *now < release_time => Span::styled(
+            format!(
+                "🔒 Unlocks in {}",
+                DurationString::new(release_time.with_timezone(&chrono::Utc) - *now)
+            ),
+            Style::default().fg(Color::Red),
+        ),
+        Some(_) => Span::styled("🔓 Unlocked", Style::default().fg(Color::Green)),
+        None => Span::raw(""),
+    };
+    Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!("🎄 Advent of Code {} - day {:02}   ", app.year, app.day),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        countdown,
+    ]))
+    .block(Block::default().borders(Borders::ALL))
+}
+
+fn description_widget(app: &App) -> Paragraph<'_> {
+    let text = app
+        .description
+        .as_deref()
+        .unwrap_or("No description. Press `d` to download it.");
+    Paragraph::new(text)
+        .wrap(Wrap { trim: false })
+        .scroll((app.description_scroll, 0))
+        .block(titled_block("Description"))
+}
+
+fn input_widget(app: &App, height: u16) -> Paragraph<'_> {
+    match &app.input {
+        Some(input) => {
+            let preview = input
+                .lines()
+                .take(height.saturating_sub(2) as usize)
+                .map(Line::raw)
+                .collect::<Vec<_>>();
+            Paragraph::new(preview).block(titled_block(format!(
+                "Input ({} lines)",
+                input.lines().count()
+            )))
+        }
+        None => Paragraph::new("No input in the cache. Press `i` to download it.")
+            .wrap(Wrap { trim: false })
+            .block(titled_block("Input")),
+    }
+}
+
+fn submissions_widget(app: &App) -> Paragraph<'_> {
+    let mut lines = Vec::new();
+    for part in [RiddlePart::One, RiddlePart::Two] {
+        lines.push(Line::styled(
+            format!("Part {}", part),
+            Style::default().add_modifier(Modifier::BOLD),
+        ));
+        let results = app
+            .submissions
+            .iter()
+            .filter(|result| result.submission.part == part)
+            .collect::<Vec<_>>();
+        if results.is_empty() {
+            lines.push(Line::raw("  no answers yet"));
+        }
+        for result in results {
+            let colour = match result.status {
+                SubmissionStatus::Correct => Color::Green,
+                SubmissionStatus::Incorrect => Color::Red,
+                _ => Color::Gray,
+            };
+            lines.push(Line::from(vec![
+                Span::raw(format!(
+                    "  {} ",
+                    result.submitted_at.format("%Y-%m-%d %H:%M")
+                )),
+                Span::styled(format!("{:?}", result.status), Style::default().fg(colour)),
+                Span::raw(format!(" {}", result.submission.answer)),
+            ]));
+        }
+    }
+    Paragraph::new(lines).block(titled_block("Submissions"))
+}
+
+fn leaderboard_widget(app: &App) -> Paragraph<'_> {
+    let lines = match (&app.leaderboard_id, &app.leaderboard) {
+        (None, _) => vec![Line::raw(
+            "Pass a leaderboard ID with `--leaderboard-id` to see it here.",
+        )],
+        (Some(_), None) => vec![Line::raw("Press `r` to download the leaderboard.")],
+        (Some(_), Some(leaderboard)) => leaderboard
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(position, entry)| {
+                let stars = entry
+                    .stars
+                    .iter()
+                    .map(|solved| match solved {
+                        SolvedParts::Both => 2,
+                        SolvedParts::One => 1,
+                        SolvedParts::None => 0,
+                    })
+                    .sum::<usize>();
+                Line::from(vec![
+                    Span::raw(format!("{:>3}) {:>5} ", position + 1, entry.points)),
+                    Span::styled(format!("{:>2}*", stars), Style::default().fg(Color::Yellow)),
+                    Span::raw(format!(" {}", entry.user)),
+                ])
+            })
+            .collect(),
+    };
+    let title = match &app.leaderboard_id {
+        Some(id) => format!("Private leaderboard {}", id),
+        None => "Private leaderboard".to_owned(),
+    };
+    Paragraph::new(lines).block(titled_block(title))
+}
+
+fn footer_widget(app: &App) -> Paragraph<'_> {
+    let first_line = match &app.prompt {
+        Some(prompt) => Line::from(vec![
+            Span::styled(
+                format!("Answer for part {}: ", prompt.part),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(format!("{}▏", prompt.answer)),
+        ]),
+        None => Line::raw(app.status.as_str()),
+    };
+    let second_line = match &app.prompt {
+        Some(_) => Line::raw("Enter submits  Tab switches the part  Esc cancels").dim(),
+        None => Line::raw(HELP).dim(),
+    };
+    Paragraph::new(vec![first_line, second_line])
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL))
+}
@@ -1,12 +1,19 @@
+pub mod answer_check;
+pub mod day_range;
 pub mod description;
+pub mod description_parts;
 pub mod duration_string;
+pub mod event;
 pub mod leaderboard;
 pub mod ports;
 pub mod private_leaderboard;
 pub mod riddle_date;
+pub mod riddle_example;
 pub mod riddle_part;
 pub(crate) mod solved_parts;
 pub mod stars;
 pub mod submission;
+pub mod submission_outcome;
+pub mod submission_preview;
 pub mod submission_result;
 pub mod submission_status;
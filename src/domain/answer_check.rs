@@ -0,0 +1,128 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AnswerError {
+    #[error("The answer is empty")]
+    Empty,
+
+    #[error("The answer spans many lines. Submit only the final result")]
+    MultiLine,
+}
+
+/// A reason to believe the answer is a mistake, even though the servers would accept it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnswerWarning {
+    /// The answer is the same as the correct answer to the first part
+    SameAsPartOne,
+
+    /// The answer is a negative number
+    Negative,
+
+    /// The answer looks like debug output rather than a result
+    LooksLikeDebugOutput,
+}
+
+impl std::fmt::Display for AnswerWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswerWarning::SameAsPartOne => {
+                write!(f, "The answer is the same as the answer to part one")
+            }
+            AnswerWarning::Negative => write!(f, "The answer is a negative number"),
+            AnswerWarning::LooksLikeDebugOutput => {
+                write!(f, "The answer looks like debug output")
+            }
+        }
+    }
+}
+
+/// Trims the answer and rejects the answers that cannot be right
+pub fn normalise_answer(answer: &str) -> Result<String, AnswerError> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        return Err(AnswerError::Empty);
+    }
+    if answer.contains(['\n', '\r']) {
+        return Err(AnswerError::MultiLine);
+    }
+    Ok(answer.to_owned())
+}
+
+/// Picks the answer out of the output of a solution - its last non-empty line
+pub fn answer_from_output(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map(str::to_owned)
+}
+
+/// Lists the reasons why the normalised answer is likely a mistake
+///
+/// `part_one_answer` is the correct answer to the first part, if the answer
+/// is meant for the second part and the first one is solved.
+pub fn check_answer(answer: &str, part_one_answer: Option<&str>) -> Vec<AnswerWarning> {
+    let mut warnings = Vec::new();
+    if part_one_answer == Some(answer) {
+        warnings.push(AnswerWarning::SameAsPartOne);
+    }
+    if answer.starts_with('-') && answer[1..].parse::<u128>().is_ok() {
+        warnings.push(AnswerWarning::Negative);
+    }
+    if looks_like_debug_output(answer) {
+        warnings.push(AnswerWarning::LooksLikeDebugOutput);
+    }
+    warnings
+}
+
+/// Spots the `Debug` representations of Rust values and labelled printouts,
+/// like `Some(42)` or `part 1: 42`
+///
+/// Punctuation on its own is not suspicious - some answers are written
+/// with it, like the SNAFU numbers of 2022 day 25.
+fn looks_like_debug_output(answer: &str) -> bool {
+    const DEBUG_MARKERS: [&str; 4] = ["Some(", "Ok(", "Err(", "None"];
+    DEBUG_MARKERS.iter().any(|marker| answer.contains(marker))
+        || answer.contains(char::is_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalises_the_answers() {
+        assert_eq!(normalise_answer("  42\n"), Ok("42".to_owned()));
+        assert_eq!(normalise_answer(" \n"), Err(AnswerError::Empty));
+        assert_eq!(normalise_answer("1\n2"), Err(AnswerError::MultiLine));
+    }
+
+    #[test]
+    fn takes_the_last_line_of_the_output() {
+        assert_eq!(
+            answer_from_output("Parsing...\nPart 1\n  1234 \n\n"),
+            Some("1234".to_owned())
+        );
+        assert_eq!(answer_from_output(" \n"), None);
+    }
+
+    #[test]
+    fn warns_about_suspicious_answers() {
+        assert!(check_answer("42", None).is_empty());
+        assert!(check_answer("abc,de-fg", Some("12")).is_empty());
+        assert!(check_answer("2=-1=0", None).is_empty());
+        assert_eq!(
+            check_answer("42", Some("42")),
+            vec![AnswerWarning::SameAsPartOne]
+        );
+        assert_eq!(check_answer("-7", None), vec![AnswerWarning::Negative]);
+        for answer in ["Some(42)", "Err(\"abc\")", "part 1: 42", "[1, 2]", "x = 3"] {
+            assert_eq!(
+                check_answer(answer, None),
+                vec![AnswerWarning::LooksLikeDebugOutput],
+                "{}",
+                answer
+            );
+        }
+    }
+}
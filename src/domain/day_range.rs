@@ -0,0 +1,90 @@
+/// An inclusive range of the days of an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayRange {
+    pub first: usize,
+    pub last: usize,
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum DayRangeError {
+    #[error("Cannot parse `{0}` as a day or a range of days. Use a day like `3` or a range like `1..=25`")]
+    Parse(String),
+
+    #[error("The range of days `{0}` is empty")]
+    Empty(String),
+}
+
+impl DayRange {
+    pub fn new(first: usize, last: usize) -> Self {
+        DayRange { first, last }
+    }
+
+    /// Returns the day if the range consists of exactly one day
+    pub fn single_day(&self) -> Option<usize> {
+        if self.first == self.last {
+            Some(self.first)
+        } else {
+            None
+        }
+    }
+
+    pub fn days(&self) -> std::ops::RangeInclusive<usize> {
+        self.first..=self.last
+    }
+}
+
+impl std::str::FromStr for DayRange {
+    type Err = DayRangeError;
+
+    /// Parses a single day (`3`), an inclusive range (`1..=25`)
+    /// or an exclusive range (`1..26`)
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let parse_day = |day: &str| {
+            day.trim()
+                .parse::<usize>()
+                .map_err(|_| DayRangeError::Parse(value.to_owned()))
+        };
+        let range = if let Some((first, last)) = value.split_once("..=") {
+            DayRange::new(parse_day(first)?, parse_day(last)?)
+        } else if let Some((first, last)) = value.split_once("..") {
+            let last = parse_day(last)?;
+            if last == 0 {
+                return Err(DayRangeError::Empty(value.to_owned()));
+            }
+            DayRange::new(parse_day(first)?, last - 1)
+        } else {
+            let day = parse_day(value)?;
+            DayRange::new(day, day)
+        };
+        if range.first > range.last {
+            return Err(DayRangeError::Empty(value.to_owned()));
+        }
+        Ok(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_single_day() {
+        assert_eq!("7".parse(), Ok(DayRange::new(7, 7)));
+        assert_eq!(DayRange::new(7, 7).single_day(), Some(7));
+    }
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!("1..=25".parse(), Ok(DayRange::new(1, 25)));
+        assert_eq!("1..26".parse(), Ok(DayRange::new(1, 25)));
+        assert_eq!(DayRange::new(1, 25).single_day(), None);
+    }
+
+    #[test]
+    fn rejects_invalid_ranges() {
+        assert!("a..=3".parse::<DayRange>().is_err());
+        assert!("5..=3".parse::<DayRange>().is_err());
+        assert!("3..3".parse::<DayRange>().is_err());
+        assert!("".parse::<DayRange>().is_err());
+    }
+}
@@ -0,0 +1,15 @@
+use super::riddle_part::RiddlePart;
+
+/// The parts of a riddle description to show
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum DescriptionParts {
+    /// Both parts of the riddle with the answers given so far
+    #[default]
+    All,
+
+    /// A single part of the riddle with its answer
+    Only(RiddlePart),
+
+    /// The most recently unlocked part - the second one after solving the first one
+    New,
+}
@@ -0,0 +1,168 @@
+use chrono::{Datelike, TimeZone};
+
+use super::day_range::DayRange;
+
+/// The year of the first Advent of Code
+pub const FIRST_YEAR: usize = 2015;
+
+/// The number of riddles of the events held until 2024
+const DAYS_UNTIL_2024: usize = 25;
+
+/// The number of riddles of the events held since 2025
+const DAYS_SINCE_2025: usize = 12;
+
+/// The offset of US Eastern Standard Time from UTC, in seconds
+///
+/// The riddles unlock at midnight in US Eastern time. The daylight saving time
+/// is never in effect in December, so the fixed offset is exact for every riddle.
+const EASTERN_OFFSET_SECS: i32 = 5 * 60 * 60;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum EventError {
+    #[error("There is no Advent of Code in {0}. The first event was held in {FIRST_YEAR}")]
+    InvalidYear(usize),
+
+    #[error("There is no day {day} in the {year} event. Its riddles are on days 1 to {days}")]
+    InvalidDay {
+        year: usize,
+        day: usize,
+        days: usize,
+    },
+}
+
+/// The Advent of Code event of a year
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    pub year: usize,
+}
+
+impl Event {
+    pub fn new(year: usize) -> Result<Self, EventError> {
+        if year < FIRST_YEAR {
+            return Err(EventError::InvalidYear(year));
+        }
+        Ok(Event { year })
+    }
+
+    /// Returns the latest event that has started by the given moment
+    pub fn latest(now: &chrono::DateTime<chrono::Utc>) -> Self {
+        let eastern_now = eastern_time(now);
+        let year = match eastern_now.month() {
+            12 => eastern_now.year() as usize,
+            _ => eastern_now.year() as usize - 1,
+        };
+        Event { year }
+    }
+
+    /// Returns the number of riddles of the event
+    pub fn days(&self) -> usize {
+        match self.year {
+            ..=2024 => DAYS_UNTIL_2024,
+            _ => DAYS_SINCE_2025,
+        }
+    }
+
+    /// Returns all the days of the event
+    pub fn day_range(&self) -> DayRange {
+        DayRange::new(1, self.days())
+    }
+
+    /// Returns true if the riddle of the day has two parts
+    ///
+    /// The last riddle of an event has a single part - its second star
+    /// is given for collecting all the other stars.
+    pub fn has_second_part(&self, day: usize) -> bool {
+        day != self.days()
+    }
+
+    pub fn validate_day(&self, day: usize) -> Result<(), EventError> {
+        if day == 0 || day > self.days() {
+            return Err(EventError::InvalidDay {
+                year: self.year,
+                day,
+                days: self.days(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the moment the riddle of the day unlocks
+    pub fn release_time(
+        &self,
+        day: usize,
+    ) -> Result<chrono::DateTime<chrono::FixedOffset>, EventError> {
+        self.validate_day(day)?;
+        Ok(eastern_offset()
+            .with_ymd_and_hms(self.year as i32, 12, day as u32, 0, 0, 0)
+            .single()
+            .expect("Every day of an event is a valid date"))
+    }
+
+    /// Returns the day of the event whose riddle is the latest unlocked at the moment,
+    /// if the event is being held
+    pub fn current_day(&self, now: &chrono::DateTime<chrono::Utc>) -> Option<usize> {
+        let eastern_now = eastern_time(now);
+        let is_held = eastern_now.year() as usize == self.year
+            && eastern_now.month() == 12
+            && eastern_now.day() as usize <= self.days();
+        is_held.then_some(eastern_now.day() as usize)
+    }
+}
+
+fn eastern_offset() -> chrono::FixedOffset {
+    chrono::FixedOffset::west_opt(EASTERN_OFFSET_SECS).expect("The offset is within a day")
+}
+
+/// Converts the moment to the time zone of the event
+pub fn eastern_time(now: &chrono::DateTime<chrono::Utc>) -> chrono::DateTime<chrono::FixedOffset> {
+    now.with_timezone(&eastern_offset())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_the_years_and_the_days() {
+        assert_eq!(Event::new(1990), Err(EventError::InvalidYear(1990)));
+        let event = Event::new(2022).unwrap();
+        assert!(event.validate_day(25).is_ok());
+        assert!(event.validate_day(0).is_err());
+        assert!(event.validate_day(31).is_err());
+    }
+
+    #[test]
+    fn has_fewer_days_since_2025() {
+        assert_eq!(Event::new(2024).unwrap().day_range(), DayRange::new(1, 25));
+        let event = Event::new(2025).unwrap();
+        assert_eq!(event.day_range(), DayRange::new(1, 12));
+        assert!(event.validate_day(13).is_err());
+        assert!(event.has_second_part(11));
+        assert!(!event.has_second_part(12));
+    }
+
+    #[test]
+    fn unlocks_the_riddles_at_midnight_eastern_time() {
+        let release_time = Event::new(2022).unwrap().release_time(3).unwrap();
+        assert_eq!(
+            release_time,
+            chrono::Utc.with_ymd_and_hms(2022, 12, 3, 5, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn follows_the_event_in_eastern_time() {
+        let before_start = chrono::Utc
+            .with_ymd_and_hms(2022, 12, 1, 4, 59, 59)
+            .unwrap();
+        assert_eq!(Event::latest(&before_start).year, 2021);
+        assert_eq!(Event::new(2022).unwrap().current_day(&before_start), None);
+
+        let start = chrono::Utc.with_ymd_and_hms(2022, 12, 1, 5, 0, 0).unwrap();
+        assert_eq!(Event::latest(&start).year, 2022);
+        assert_eq!(Event::new(2022).unwrap().current_day(&start), Some(1));
+
+        let after_end = chrono::Utc.with_ymd_and_hms(2022, 12, 26, 5, 0, 0).unwrap();
+        assert_eq!(Event::new(2022).unwrap().current_day(&after_end), None);
+    }
+}
@@ -19,7 +19,7 @@ pub enum AocClientError {
     #[error("URL parsing")]
     URLParsingError(#[from] url::ParseError),
 
-    #[error("Runtime error")]
+    #[error(transparent)]
     RuntimeError(#[from] anyhow::Error),
 
     #[error("Network error")]
@@ -1,14 +1,15 @@
-use super::solved_parts::SolvedParts;
+use super::{event::Event, solved_parts::SolvedParts};
 
 #[derive(Debug, Clone)]
 pub struct PrivateLeaderboard {
+    pub event: Event,
     pub entries: Vec<PrivateLeaderboardEntry>,
 }
 
 impl PrivateLeaderboard {
-    pub fn new(mut entries: Vec<PrivateLeaderboardEntry>) -> Self {
+    pub fn new(event: Event, mut entries: Vec<PrivateLeaderboardEntry>) -> Self {
         entries.sort_by(|first, second| second.points.cmp(&first.points));
-        Self { entries }
+        Self { event, entries }
     }
 }
 
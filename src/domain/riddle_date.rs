@@ -1,7 +1,12 @@
+use super::event::{Event, EventError};
+
 #[derive(thiserror::Error, Debug, PartialEq)]
 pub enum RiddleDateError {
     #[error("Could not guess the riddle date based on the current date")]
     GuessError,
+
+    #[error(transparent)]
+    Event(#[from] EventError),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -15,79 +20,106 @@ impl RiddleDate {
         RiddleDate { year, day }
     }
 
-    pub fn best_guess<Date: chrono::Datelike>(
+    /// Works out the riddle from the given year and day, filling in the missing ones
+    /// from the event clock
+    ///
+    /// The riddle unlocked most recently is picked while an event is being held.
+    /// A day without a year belongs to the latest event.
+    pub fn best_guess(
         year: Option<usize>,
         day: Option<usize>,
-        current_date: Date,
-    ) -> Result<Self, RiddleDateError> {
-        match (year, day) {
-            (Some(year), Some(day)) => Ok(Self::new(year, day)),
-            (None, None) => Self::guess_from_current_date(current_date),
-            (None, Some(day)) => Self::guess_from_day(day, current_date),
-            (Some(_), None) => Err(RiddleDateError::GuessError),
-        }
-    }
-
-    fn guess_from_current_date<Date: chrono::Datelike>(
-        current_date: Date,
-    ) -> Result<Self, RiddleDateError> {
-        if current_date.month() == 12 && current_date.day() <= 25 {
-            Ok(Self::new(
-                current_date.year() as usize,
-                current_date.day() as usize,
-            ))
-        } else {
-            Err(RiddleDateError::GuessError)
-        }
-    }
-
-    fn guess_from_day<Date: chrono::Datelike>(
-        day: usize,
-        current_date: Date,
+        now: &chrono::DateTime<chrono::Utc>,
     ) -> Result<Self, RiddleDateError> {
-        if current_date.month() == 12 {
-            Ok(Self::new(current_date.year() as usize, day))
-        } else {
-            Ok(Self::new(current_date.year() as usize - 1, day))
-        }
+        let event = match year {
+            Some(year) => Event::new(year)?,
+            None => Event::latest(now),
+        };
+        let day = match day {
+            Some(day) => day,
+            None => event.current_day(now).ok_or(RiddleDateError::GuessError)?,
+        };
+        event.validate_day(day)?;
+        Ok(Self::new(event.year, day))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use chrono::TimeZone;
+
     use super::*;
 
+    fn eastern_noon(year: i32, month: u32, day: u32) -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc
+            .with_ymd_and_hms(year, month, day, 17, 0, 0)
+            .unwrap()
+    }
+
     #[test]
     fn guess_from_the_exact_year_and_day() {
-        let expected = RiddleDate::new(1, 1);
+        let expected = RiddleDate::new(2015, 1);
         assert_eq!(
             Ok(expected),
-            RiddleDate::best_guess(Some(1), Some(1), chrono::Utc::now())
+            RiddleDate::best_guess(Some(2015), Some(1), &chrono::Utc::now())
+        );
+    }
+
+    #[test]
+    fn rejects_the_dates_outside_of_the_events() {
+        let now = chrono::Utc::now();
+        assert_eq!(
+            RiddleDate::best_guess(Some(1990), Some(1), &now),
+            Err(RiddleDateError::Event(EventError::InvalidYear(1990)))
         );
+        assert!(RiddleDate::best_guess(Some(2022), Some(31), &now).is_err());
+        assert!(RiddleDate::best_guess(None, Some(0), &now).is_err());
     }
 
     #[test]
     fn guess_current_date_if_its_december() {
-        let now = chrono::NaiveDate::from_ymd_opt(2023, 12, 3).unwrap();
         let expected = RiddleDate::new(2023, 3);
-        assert_eq!(Ok(expected), RiddleDate::best_guess(None, None, now));
+        assert_eq!(
+            Ok(expected),
+            RiddleDate::best_guess(None, None, &eastern_noon(2023, 12, 3))
+        );
+    }
+
+    #[test]
+    fn guess_the_day_in_eastern_time() {
+        // 3 AM in UTC is still the previous day in Eastern time
+        let now = chrono::Utc.with_ymd_and_hms(2023, 12, 4, 3, 0, 0).unwrap();
+        assert_eq!(
+            Ok(RiddleDate::new(2023, 3)),
+            RiddleDate::best_guess(None, None, &now)
+        );
     }
 
     #[test]
     fn cannot_guess_from_current_date_if_its_not_december() {
-        let now = chrono::NaiveDate::from_ymd_opt(2023, 11, 1).unwrap();
-        assert!(RiddleDate::best_guess(None, None, now).is_err());
+        let now = eastern_noon(2023, 11, 1);
+        assert!(RiddleDate::best_guess(None, None, &now).is_err());
     }
 
     #[test]
     fn guess_last_year_if_the_year_is_not_given() {
-        let now = chrono::NaiveDate::from_ymd_opt(2023, 7, 1).unwrap();
         let expected = RiddleDate::new(2022, 3);
-        assert_eq!(Ok(expected), RiddleDate::best_guess(None, Some(3), now));
+        assert_eq!(
+            Ok(expected),
+            RiddleDate::best_guess(None, Some(3), &eastern_noon(2023, 7, 1))
+        );
+    }
+
+    #[test]
+    fn guess_the_day_if_the_year_is_the_current_event() {
+        let now = eastern_noon(2023, 12, 5);
+        let expected = RiddleDate::new(2023, 5);
+        assert_eq!(Ok(expected), RiddleDate::best_guess(Some(2023), None, &now));
+        assert!(RiddleDate::best_guess(Some(2022), None, &now).is_err());
     }
 
     #[test]
     fn cannot_guess_if_only_the_year_is_provided() {
-        assert!(RiddleDate::best_guess(Some(2023), None, chrono::Utc::now()).is_err());
+        let now = eastern_noon(2023, 7, 1);
+        assert!(RiddleDate::best_guess(Some(2023), None, &now).is_err());
     }
 }
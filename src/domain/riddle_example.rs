@@ -0,0 +1,40 @@
+use super::riddle_part::RiddlePart;
+
+/// Where an example of a riddle comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExampleSource {
+    /// Found in the text of the riddle
+    Description,
+
+    /// Written down by the user in the examples file
+    ExamplesFile,
+}
+
+impl std::fmt::Display for ExampleSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExampleSource::Description => write!(f, "the description"),
+            ExampleSource::ExamplesFile => write!(f, "the examples file"),
+        }
+    }
+}
+
+/// A small input of a riddle with a known answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RiddleExample {
+    pub part: RiddlePart,
+    pub input: String,
+    pub answer: String,
+    pub source: ExampleSource,
+}
+
+impl RiddleExample {
+    pub fn new(part: RiddlePart, input: String, answer: String, source: ExampleSource) -> Self {
+        RiddleExample {
+            part,
+            input,
+            answer,
+            source,
+        }
+    }
+}
@@ -1,6 +1,8 @@
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, clap::ValueEnum)]
 pub enum RiddlePart {
+    #[serde(alias = "one")]
     One = 1,
+    #[serde(alias = "two")]
     Two = 2,
 }
 
@@ -3,11 +3,17 @@ use super::solved_parts::SolvedParts;
 pub struct Stars {
     pub stars: Vec<SolvedParts>,
     pub pattern: Vec<String>,
+    /// The rows of the calendar with the colours of the ASCII art
+    pub calendar: Vec<CalendarRow>,
 }
 
 impl Stars {
-    pub fn new(stars: Vec<SolvedParts>, pattern: Vec<String>) -> Self {
-        Stars { stars, pattern }
+    pub fn new(stars: Vec<SolvedParts>, pattern: Vec<String>, calendar: Vec<CalendarRow>) -> Self {
+        Stars {
+            stars,
+            pattern,
+            calendar,
+        }
     }
 }
 
@@ -16,3 +22,55 @@ impl core::fmt::Display for Stars {
         write!(f, "{}\n", self.pattern.join("\n").to_string())
     }
 }
+
+/// A line of the calendar
+///
+/// The lines of the days end with the number of the day. The stars
+/// of the day are not part of the cells, `solved` tells which to draw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarRow {
+    pub day: Option<usize>,
+    pub solved: SolvedParts,
+    pub cells: Vec<CalendarCell>,
+}
+
+/// A character of the calendar art with its colour, if the page gives one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarCell {
+    pub character: char,
+    pub colour: Option<Colour>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Colour {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Colour {
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Colour { red, green, blue }
+    }
+
+    /// Parses a colour written like `#7fbd39`
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#')?;
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+        Some(Colour::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    /// Writes the colour like `#7fbd39`
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+}
+
+/// The colour of the stars of the fully solved riddles
+pub const GOLD: Colour = Colour::new(0xff, 0xff, 0x66);
+
+/// The colour of the stars of the riddles with only the first part solved
+pub const SILVER: Colour = Colour::new(0x99, 0x99, 0xcc);
@@ -0,0 +1,50 @@
+use super::{
+    answer_check::AnswerWarning, duration_string::DurationString,
+    submission_result::SubmissionResult,
+};
+
+/// What happened to an answer handed over to `elv`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    /// The part was solved before, so the answer was not sent
+    AlreadySolved(SubmissionResult),
+
+    /// The same answer was sent before and its result is reused
+    Repeated {
+        result: SubmissionResult,
+        wait_time: Option<chrono::Duration>,
+    },
+
+    /// The previous answer was wrong and the servers asked to wait before the next one
+    TooSoon(chrono::Duration),
+
+    /// The answer looks like a mistake, so it was not sent
+    Suspicious(Vec<AnswerWarning>),
+
+    /// The answer was sent to the servers
+    Submitted(SubmissionResult),
+}
+
+impl std::fmt::Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::AlreadySolved(result)
+            | SubmissionOutcome::Repeated { result, .. }
+            | SubmissionOutcome::Submitted(result) => write!(f, "{}", result.message),
+            SubmissionOutcome::TooSoon(wait_time) => write!(
+                f,
+                "You wanted to submit an answer too soon. Please wait {} before submitting again.",
+                DurationString::new(*wait_time)
+            ),
+            SubmissionOutcome::Suspicious(warnings) => write!(
+                f,
+                "The answer was not submitted. {}.",
+                warnings
+                    .iter()
+                    .map(|warning| warning.to_string())
+                    .collect::<Vec<_>>()
+                    .join(". ")
+            ),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use super::{submission::Submission, submission_outcome::SubmissionOutcome};
+
+/// What submitting an answer would do, worked out without sending it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmissionPreview {
+    /// The answer with the riddle it is meant for
+    pub submission: Submission,
+
+    /// The outcome known without asking the servers, if there is one
+    pub known_outcome: Option<SubmissionOutcome>,
+
+    /// The HTTP request sending the answer
+    pub request: String,
+}
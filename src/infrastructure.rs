@@ -1,9 +1,19 @@
+pub(crate) mod answers_file;
 pub(crate) mod aoc_api;
+pub(crate) mod cache_inventory;
+pub(crate) mod calendar_image;
 pub(crate) mod cli_display;
 pub(crate) mod configuration;
+pub(crate) mod configuration_keys;
 pub mod driver;
-mod find_riddle_part;
+pub(crate) mod examples_file;
 pub(crate) mod http_description;
 pub(crate) mod input_cache;
+pub(crate) mod page_cache;
+pub(crate) mod path_template;
 mod private_leaderboard;
+pub(crate) mod retry_policy;
+pub(crate) mod rich_text;
+pub(crate) mod solution_runner;
 pub(crate) mod submission_history;
+pub(crate) mod throttle;
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::domain::riddle_part::RiddlePart;
+
+#[derive(Error, Debug)]
+pub enum AnswersFileError {
+    #[error("Failed to read the answers file {0}")]
+    Read(String),
+
+    #[error("The answers file {0} is not valid: {1}")]
+    Parse(String, String),
+}
+
+/// The correct answers to the riddles, kept along the solutions
+///
+/// The file is written in `TOML` with a table for every day:
+///
+/// ```toml
+/// [2022.1]
+/// one = "69528"
+/// two = "206152"
+/// ```
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(transparent)]
+pub struct AnswersFile {
+    years: HashMap<String, HashMap<String, DayAnswers>>,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+struct DayAnswers {
+    one: Option<String>,
+    two: Option<String>,
+}
+
+impl AnswersFile {
+    /// Reads the answers file, or returns no answers if the file does not exist
+    pub fn load(path: &std::path::Path) -> Result<Self, AnswersFileError> {
+        if !path.exists() {
+            return Ok(AnswersFile::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|_| AnswersFileError::Read(path.display().to_string()))?;
+        Self::parse(&contents)
+            .map_err(|e| AnswersFileError::Parse(path.display().to_string(), e.to_string()))
+    }
+
+    fn parse(contents: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(contents)
+    }
+
+    pub fn answer(&self, year: usize, day: usize, part: &RiddlePart) -> Option<String> {
+        let answers = self.years.get(&year.to_string())?.get(&day.to_string())?;
+        match part {
+            RiddlePart::One => answers.one.clone(),
+            RiddlePart::Two => answers.two.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_answers_of_a_day() {
+        let answers = AnswersFile::parse(
+            r#"
+[2022.1]
+one = "69528"
+two = "206152"
+
+[2022.25]
+one = "2=-1=0"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            answers.answer(2022, 1, &RiddlePart::Two),
+            Some("206152".to_owned())
+        );
+        assert_eq!(
+            answers.answer(2022, 25, &RiddlePart::One),
+            Some("2=-1=0".to_owned())
+        );
+        assert_eq!(answers.answer(2022, 25, &RiddlePart::Two), None);
+        assert_eq!(answers.answer(2021, 1, &RiddlePart::One), None);
+    }
+}
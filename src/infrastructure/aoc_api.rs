@@ -1,17 +1,18 @@
-use super::configuration::Configuration;
+use super::{configuration::Configuration, retry_policy::RetryPolicy, throttle::Throttle};
 
-const AOC_URL: &str = "https://adventofcode.com";
+pub(crate) const AOC_URL: &str = "https://adventofcode.com";
 
 #[derive(Debug)]
 pub struct AocApi {
     http_client: reqwest::blocking::Client,
     configuration: Configuration,
+    throttle: Throttle,
+    retry_policy: RetryPolicy,
 }
 
 mod aoc_api_impl;
 pub mod aoc_client_impl;
-pub mod find_riddle_part_impl;
+pub mod get_input_impl;
 pub mod get_leaderboard_impl;
 pub mod get_private_leaderboard_impl;
 pub mod get_stars_impl;
-pub mod get_input_impl;
@@ -1,53 +1,213 @@
 use anyhow::Context;
 
-use super::super::configuration::Configuration;
+use super::super::{
+    configuration::Configuration,
+    throttle::{parse_retry_after, EndpointClass, ThrottleError},
+};
+use crate::domain::duration_string::DurationString;
 
-use super::{AocApi, AOC_URL};
+use super::{AocApi, RetryPolicy, Throttle, AOC_URL};
+
+/// The wait after a `429 Too Many Requests` response without the `Retry-After` header
+const DEFAULT_RETRY_AFTER_SECS: i64 = 60;
 
 impl AocApi {
     pub fn new(http_client: reqwest::blocking::Client, configuration: Configuration) -> AocApi {
         Self {
             http_client,
+            throttle: Throttle::new(configuration.throttle),
+            retry_policy: RetryPolicy::new(configuration.retry),
             configuration,
         }
     }
 
-    pub fn prepare_http_client(configuration: &Configuration) -> reqwest::blocking::Client {
+    /// Sends the request, retrying it after transient failures
+    ///
+    /// Connection errors, timeouts and server errors are retried
+    /// according to the retry policy. Answers are never retried.
+    pub(super) fn send(
+        &self,
+        class: EndpointClass,
+        request: reqwest::blocking::RequestBuilder,
+    ) -> anyhow::Result<reqwest::blocking::Response> {
+        let attempts = if class.is_idempotent() {
+            self.retry_policy.attempts()
+        } else {
+            1
+        };
+        let mut attempt = 1;
+        loop {
+            let attempted_request = match request.try_clone() {
+                Some(attempted_request) if attempt < attempts => attempted_request,
+                _ => return self.send_throttled(class, request),
+            };
+            let failure = match self.send_throttled(class, attempted_request) {
+                Ok(response) if response.status().is_server_error() => {
+                    format!("The AOC server responded with {}", response.status())
+                }
+                Err(e) if RetryPolicy::is_transient(&e) => {
+                    format!("The request failed: {}", e.root_cause())
+                }
+                outcome => return outcome,
+            };
+            let delay = self.retry_policy.delay(attempt);
+            eprintln!(
+                "⚠️ {}. Retrying in {:.1}s (attempt {}/{})...",
+                failure,
+                delay.as_secs_f64(),
+                attempt + 1,
+                attempts
+            );
+            std::thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
+    /// Sends the request once the throttle allows it
+    ///
+    /// If the servers respond with `429 Too Many Requests`, the request is
+    /// repeated once after the time given in the `Retry-After` header,
+    /// provided it is not an answer submission and the wait is short enough.
+    fn send_throttled(
+        &self,
+        class: EndpointClass,
+        request: reqwest::blocking::RequestBuilder,
+    ) -> anyhow::Result<reqwest::blocking::Response> {
+        let repeated_request = request.try_clone();
+        self.throttle.acquire(class)?;
+        let response = request.send().context(
+            "Failed to send the request to the AOC server. Is your internet connection OK?",
+        )?;
+        if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Ok(response);
+        }
+
+        let wait = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, &chrono::Utc::now()))
+            .unwrap_or_else(|| chrono::Duration::seconds(DEFAULT_RETRY_AFTER_SECS));
+        self.throttle.block(class, wait);
+        match repeated_request {
+            Some(repeated_request) if class.is_idempotent() && wait <= self.throttle.max_wait() => {
+                self.throttle.acquire(class)?;
+                repeated_request.send().context(
+                    "Failed to send the request to the AOC server. Is your internet connection OK?",
+                )
+            }
+            _ => Err(ThrottleError::TooManyRequests(format!(
+                "The Advent of Code servers received too many requests from you. Try again in {}",
+                DurationString::new(wait)
+            ))
+            .into()),
+        }
+    }
+
+    pub fn prepare_http_client(
+        configuration: &Configuration,
+    ) -> anyhow::Result<reqwest::blocking::Client> {
         let cookie = format!("session={}", configuration.aoc.token);
         let url = AOC_URL.parse::<reqwest::Url>().expect("Invalid URL");
         let jar = reqwest::cookie::Jar::default();
         jar.add_cookie_str(&cookie, &url);
 
-        reqwest::blocking::Client::builder()
+        let http = &configuration.http;
+        let mut client_builder = reqwest::blocking::Client::builder()
             .cookie_provider(std::sync::Arc::new(jar))
-            .user_agent(Self::aoc_elf_user_agent())
+            .user_agent(Self::aoc_elf_user_agent(http.user_agent_contact.as_deref()))
+            .connect_timeout(std::time::Duration::from_secs(http.connect_timeout_secs))
+            .timeout(std::time::Duration::from_secs(http.request_timeout_secs));
+
+        if let Some(proxy_url) = &http.proxy {
+            let proxy = reqwest::Proxy::all(proxy_url)
+                .with_context(|| format!("Invalid proxy URL: {}", proxy_url))?
+                .no_proxy(
+                    http.no_proxy
+                        .as_deref()
+                        .and_then(reqwest::NoProxy::from_string),
+                );
+            client_builder = client_builder.proxy(proxy);
+        }
+
+        if let Some(ca_bundle) = &http.ca_bundle {
+            let pem_bundle = std::fs::read(ca_bundle).with_context(|| {
+                format!("Failed to read the CA bundle: {}", ca_bundle.display())
+            })?;
+            let certificates =
+                reqwest::Certificate::from_pem_bundle(&pem_bundle).with_context(|| {
+                    format!(
+                        "Failed to parse the certificates in the CA bundle: {}",
+                        ca_bundle.display()
+                    )
+                })?;
+            if certificates.is_empty() {
+                anyhow::bail!(
+                    "The CA bundle does not contain any PEM certificates: {}",
+                    ca_bundle.display()
+                );
+            }
+            for certificate in certificates {
+                client_builder = client_builder.add_root_certificate(certificate);
+            }
+        }
+
+        client_builder
             .build()
-            .expect("Error building the HTTP client")
+            .context("Error building the HTTP client")
     }
 
-    pub fn aoc_elf_user_agent() -> String {
+    pub fn aoc_elf_user_agent(contact: Option<&str>) -> String {
         let pkg_name: &str = env!("CARGO_PKG_NAME");
         let pkg_version: &str = env!("CARGO_PKG_VERSION");
 
-        format!(
-            "{}/{} (+{} author:{})",
-            pkg_name, pkg_version, "https://github.com/kpagacz/elv", "konrad.pagacz@gmail.com"
-        )
+        match contact {
+            Some(contact) => format!(
+                "{}/{} (+{} author:{} contact:{})",
+                pkg_name,
+                pkg_version,
+                "https://github.com/kpagacz/elv",
+                "konrad.pagacz@gmail.com",
+                contact
+            ),
+            None => format!(
+                "{}/{} (+{} author:{})",
+                pkg_name, pkg_version, "https://github.com/kpagacz/elv", "konrad.pagacz@gmail.com"
+            ),
+        }
     }
 
+    /// Reads the wait before the next submission from the reply to an answer
+    ///
+    /// A wrong answer asks to "please wait N minutes" and an answer given
+    /// too soon says "You have [Nm ]Ns left to wait". Returns a zero wait
+    /// if the message mentions neither.
     pub fn extract_wait_time_from_message(message: &str) -> std::time::Duration {
-        let please_wait_marker = "lease wait ";
-        let please_wait_position = match message.find(please_wait_marker) {
-            Some(position) => position,
-            None => return std::time::Duration::new(0, 0),
-        };
-        let minutes_position = please_wait_position + please_wait_marker.len();
-        let next_space_position = message[minutes_position..].find(' ').unwrap();
-        let minutes = &message[minutes_position..minutes_position + next_space_position];
-        if minutes == "one" {
-            std::time::Duration::from_secs(60)
-        } else {
-            std::time::Duration::from_secs(60 * minutes.parse::<u64>().unwrap_or(0))
+        let left_to_wait_regex =
+            regex::Regex::new(r"(?i)you\s+have\s+(?:(\d+)m\s*)?(?:(\d+)s\s+)?left\s+to\s+wait")
+                .expect("Parsing a static regex should not fail");
+        if let Some(captures) = left_to_wait_regex.captures(message) {
+            let number = |index| {
+                captures
+                    .get(index)
+                    .and_then(|number: regex::Match| number.as_str().parse::<u64>().ok())
+                    .unwrap_or(0)
+            };
+            return std::time::Duration::from_secs(60 * number(1) + number(2));
+        }
+
+        let please_wait_regex = regex::Regex::new(r"(?i)please\s+wait\s+(one|\d+)\s+minute")
+            .expect("Parsing a static regex should not fail");
+        match please_wait_regex
+            .captures(message)
+            .and_then(|captures| captures.get(1))
+            .map(|minutes| minutes.as_str())
+        {
+            Some("one") => std::time::Duration::from_secs(60),
+            Some(minutes) => {
+                std::time::Duration::from_secs(60 * minutes.parse::<u64>().unwrap_or(0))
+            }
+            None => std::time::Duration::new(0, 0),
         }
     }
 
@@ -68,3 +228,43 @@ impl AocApi {
         Ok(answer_text)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_the_client_with_the_http_settings() {
+        let mut configuration = Configuration::default();
+        configuration.http.proxy = Some("http://proxy.example.com:8080".to_owned());
+        configuration.http.no_proxy = Some("localhost,127.0.0.1".to_owned());
+        configuration.http.connect_timeout_secs = 1;
+        configuration.http.request_timeout_secs = 2;
+        assert!(AocApi::prepare_http_client(&configuration).is_ok());
+    }
+
+    #[test]
+    fn rejects_an_invalid_proxy() {
+        let mut configuration = Configuration::default();
+        configuration.http.proxy = Some("http://[::1".to_owned());
+        let error = AocApi::prepare_http_client(&configuration).unwrap_err();
+        assert!(error.to_string().contains("Invalid proxy URL"));
+    }
+
+    #[test]
+    fn rejects_a_missing_or_empty_ca_bundle() {
+        let mut configuration = Configuration::default();
+        configuration.http.ca_bundle = Some(std::path::PathBuf::from("does-not-exist.pem"));
+        let error = AocApi::prepare_http_client(&configuration).unwrap_err();
+        assert!(error.to_string().contains("Failed to read the CA bundle"));
+
+        let empty_bundle = std::env::temp_dir().join("elv-empty-ca-bundle.pem");
+        std::fs::write(&empty_bundle, "no certificates here").unwrap();
+        configuration.http.ca_bundle = Some(empty_bundle.clone());
+        let error = AocApi::prepare_http_client(&configuration).unwrap_err();
+        std::fs::remove_file(&empty_bundle).unwrap();
+        assert!(error
+            .to_string()
+            .contains("does not contain any PEM certificates"));
+    }
+}
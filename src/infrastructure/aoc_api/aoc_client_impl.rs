@@ -6,38 +6,86 @@ use crate::domain::{
     submission_result::SubmissionResult,
     submission_status::SubmissionStatus,
 };
-use reqwest::header::{CONTENT_TYPE, ORIGIN};
+use crate::infrastructure::{configuration::Configuration, throttle::EndpointClass};
+use reqwest::header::ORIGIN;
 use std::io::Read;
 
-impl AocClient for AocApi {
-    fn submit_answer(&self, submission: Submission) -> Result<SubmissionResult, AocClientError> {
-        let url = reqwest::Url::parse(&format!(
+impl AocApi {
+    fn answer_url(submission: &Submission) -> Result<reqwest::Url, AocClientError> {
+        reqwest::Url::parse(&format!(
             "{}/{}/day/{}/answer",
             AOC_URL, submission.year, submission.day
         ))
-        .map_err(|e| AocClientError::SubmitAnswerError(e.to_string()))?;
-        let mut response = self
-            .http_client
-            .post(url)
-            .body(format!(
-                "level={}&answer={}",
+        .map_err(|e| AocClientError::SubmitAnswerError(e.to_string()))
+    }
+
+    /// The fields of the form sending the answer
+    fn answer_form(submission: &Submission) -> [(&'static str, &str); 2] {
+        [
+            (
+                "level",
                 match submission.part {
-                    RiddlePart::One => 1,
-                    RiddlePart::Two => 2,
+                    RiddlePart::One => "1",
+                    RiddlePart::Two => "2",
                 },
-                submission.answer
-            ))
-            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            ),
+            ("answer", submission.answer.as_str()),
+        ]
+    }
+
+    fn answer_referer(submission: &Submission) -> String {
+        format!(
+            "https://adventofcode.com/{}/day/{}",
+            submission.year, submission.day
+        )
+    }
+
+    fn answer_request(
+        &self,
+        submission: &Submission,
+    ) -> Result<reqwest::blocking::RequestBuilder, AocClientError> {
+        Ok(self
+            .http_client
+            .post(Self::answer_url(submission)?)
+            .form(&Self::answer_form(submission))
             .header(ORIGIN, "https://adventofcode.com")
-            .header(
-                "Referer",
-                format!(
-                    "https://adventofcode.com/{}/day/{}",
-                    submission.year, submission.day
-                ),
-            )
-            .send()
-            .map_err(|e| AocClientError::SubmitAnswerError(e.to_string()))?;
+            .header("Referer", Self::answer_referer(submission)))
+    }
+
+    /// Describes the HTTP request that would send the answer, with the session token hidden
+    ///
+    /// It does not need the HTTP client, so it works offline and with broken
+    /// connection settings, too.
+    pub fn describe_answer_request(
+        submission: &Submission,
+        configuration: &Configuration,
+    ) -> Result<String, AocClientError> {
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(Self::answer_form(submission))
+            .finish();
+        Ok([
+            format!("POST {}", Self::answer_url(submission)?),
+            "content-type: application/x-www-form-urlencoded".to_owned(),
+            "origin: https://adventofcode.com".to_owned(),
+            format!("referer: {}", Self::answer_referer(submission)),
+            format!(
+                "user-agent: {}",
+                Self::aoc_elf_user_agent(configuration.http.user_agent_contact.as_deref())
+            ),
+            "cookie: session=<your session token>".to_owned(),
+            String::new(),
+            body,
+        ]
+        .join("\n"))
+    }
+}
+
+impl AocClient for AocApi {
+    fn submit_answer(&self, submission: Submission) -> Result<SubmissionResult, AocClientError> {
+        let request = self.answer_request(&submission)?;
+        let mut response = self
+            .send(EndpointClass::Answer, request)
+            .map_err(|e| AocClientError::SubmitAnswerError(format!("{:#}", e)))?;
         if !response.status().is_success() {
             return Err(AocClientError::SubmitAnswerError(format!(
                 "Status code: {}",
@@ -83,9 +131,7 @@ impl AocClient for AocApi {
         day: usize,
     ) -> Result<HttpDescription, AocClientError> {
         let url = reqwest::Url::parse(&format!("{}/{}/day/{}", AOC_URL, year, day))?;
-        self.http_client
-            .get(url)
-            .send()?
+        self.send(EndpointClass::Page, self.http_client.get(url))?
             .try_into()
             .map_err(|_e| AocClientError::GetDescriptionError)
     }
@@ -111,6 +157,72 @@ mod tests {
         assert_eq!(wait_time, std::time::Duration::from_secs(2 * 60));
     }
 
+    #[test]
+    fn extraction_of_wait_time_from_wrapped_message() {
+        let message = "Because you have guessed incorrectly 7 times on this\npuzzle, please\nwait 10 minutes before trying again.";
+        let wait_time = AocApi::extract_wait_time_from_message(message);
+        assert_eq!(wait_time, std::time::Duration::from_secs(10 * 60));
+    }
+
+    fn submission_message(resource: &str) -> String {
+        let body = std::fs::read_to_string(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/resources")
+                .join(resource),
+        )
+        .unwrap();
+        let configuration = Configuration::default();
+        let http_client = AocApi::prepare_http_client(&configuration).unwrap();
+        let api = AocApi::new(http_client, configuration);
+        api.parse_submission_answer_body(&body).unwrap()
+    }
+
+    #[test]
+    fn extraction_of_wait_time_from_answers_given_too_soon() {
+        let message = submission_message("submission-output-toosoon-seconds.html");
+        assert!(message.starts_with("You gave an answer too recently"));
+        assert_eq!(
+            AocApi::extract_wait_time_from_message(&message),
+            std::time::Duration::from_secs(46)
+        );
+
+        let message = submission_message("submission-output-toosoon-minutes-seconds.html");
+        assert_eq!(
+            AocApi::extract_wait_time_from_message(&message),
+            std::time::Duration::from_secs(4 * 60 + 47)
+        );
+    }
+
+    #[test]
+    fn extraction_of_wait_time_from_wrong_answers() {
+        let message = submission_message("submission-output-incorrect-1st-time.html");
+        assert_eq!(
+            AocApi::extract_wait_time_from_message(&message),
+            std::time::Duration::from_secs(60)
+        );
+
+        let message = submission_message("submission-output-incorrect-7th-time.html");
+        assert_eq!(
+            AocApi::extract_wait_time_from_message(&message),
+            std::time::Duration::from_secs(10 * 60)
+        );
+    }
+
+    #[test]
+    fn describes_the_answer_request_without_the_session_token() {
+        let mut configuration = Configuration::default();
+        configuration.aoc.token = "secret".to_owned();
+        let submission = Submission::new(RiddlePart::Two, "a&b c".to_owned(), 2020, 1);
+
+        let request = AocApi::describe_answer_request(&submission, &configuration).unwrap();
+
+        assert!(request.starts_with("POST https://adventofcode.com/2020/day/1/answer\n"));
+        assert!(request.contains("referer: https://adventofcode.com/2020/day/1\n"));
+        assert!(request.contains("cookie: session=<your session token>\n"));
+        assert!(!request.contains("secret"));
+        assert!(request.ends_with("\n\nlevel=2&answer=a%26b+c"));
+    }
+
     #[test]
     fn test_parse_submission_answer_body() {
         let body = r#"
@@ -121,7 +233,7 @@ mod tests {
 </main>"#;
 
         let configuration = Configuration::default();
-        let api_client = AocApi::prepare_http_client(&configuration);
+        let api_client = AocApi::prepare_http_client(&configuration).unwrap();
         let api = AocApi::new(api_client, configuration);
         let message = api.parse_submission_answer_body(body).unwrap();
         assert_eq!(message, "That's the right answer! You are one gold star closer to saving your vacation. [Continue to Part Two]\n");
@@ -141,7 +253,7 @@ mod tests {
         "#;
 
         let configuration = Configuration::default();
-        let http_client = AocApi::prepare_http_client(&configuration);
+        let http_client = AocApi::prepare_http_client(&configuration).unwrap();
         let api = AocApi::new(http_client, configuration);
         let message = api.parse_submission_answer_body(body).unwrap();
         assert_eq!(message, concat!(
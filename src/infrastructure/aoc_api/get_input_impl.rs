@@ -2,6 +2,7 @@ use std::io::Read;
 
 use super::{AocApi, AOC_URL};
 use crate::domain::ports::get_input::GetInput;
+use crate::infrastructure::throttle::EndpointClass;
 use anyhow::{Context, Result};
 
 impl GetInput for AocApi {
@@ -12,12 +13,7 @@ impl GetInput for AocApi {
                 AOC_URL, year, day
             ),
         )?;
-        self.http_client
-            .get(url)
-            .send()
-            .context(
-                "Failed to send the request to the AOC server. Is your internet connection OK?",
-            )
+        self.send(EndpointClass::Input, self.http_client.get(url))
             .and_then(|response| {
                 response.error_for_status().context(format!(
                     "Got a non-200 response from a server. Is your token up to date?",
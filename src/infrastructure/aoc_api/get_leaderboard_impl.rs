@@ -6,11 +6,14 @@ use crate::domain::{
 };
 
 use super::{AocApi, AOC_URL};
+use crate::infrastructure::throttle::EndpointClass;
 
 impl GetLeaderboard for AocApi {
     fn get_leaderboard(&self, year: i32) -> Result<Leaderboard, AocClientError> {
         let url = reqwest::Url::parse(&format!("{}/{}/leaderboard", AOC_URL, year))?;
-        let mut response = self.http_client.get(url).send()?.error_for_status()?;
+        let mut response = self
+            .send(EndpointClass::Page, self.http_client.get(url))?
+            .error_for_status()?;
         let mut body = String::from("");
         response.read_to_string(&mut body)?;
 
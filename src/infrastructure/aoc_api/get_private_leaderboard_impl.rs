@@ -4,7 +4,11 @@ use crate::domain::{
     ports::{errors::AocClientError, get_private_leaderboard::GetPrivateLeaderboard},
     private_leaderboard::PrivateLeaderboard,
 };
-use crate::infrastructure::private_leaderboard::private_leaderboard_response::PrivateLeaderboardResponse;
+use crate::infrastructure::{
+    page_cache::{CachedPage, FilePageCache},
+    private_leaderboard::private_leaderboard_response::PrivateLeaderboardResponse,
+    throttle::EndpointClass,
+};
 
 use super::{AocApi, AOC_URL};
 
@@ -18,8 +22,21 @@ impl GetPrivateLeaderboard for AocApi {
             "{}/{}/leaderboard/private/view/{}.json",
             AOC_URL, year, leaderboard_id
         ))?;
-        let response = self.http_client.get(url).send()?.error_for_status()?;
-        PrivateLeaderboard::from_json(response)
+        let body = self
+            .send(EndpointClass::PrivateLeaderboard, self.http_client.get(url))?
+            .error_for_status()?
+            .text()?;
+        // Only a leaderboard is cached, not a login page or an error
+        let private_leaderboard = PrivateLeaderboard::from_json(body.as_bytes())?;
+        FilePageCache::save(
+            &CachedPage::PrivateLeaderboard {
+                year,
+                id: leaderboard_id.to_owned(),
+            },
+            &body,
+        )
+        .map_err(anyhow::Error::from)?;
+        Ok(private_leaderboard)
     }
 }
 
@@ -30,8 +47,8 @@ impl PrivateLeaderboard {
             .read_to_string(&mut body)
             .map_err(|_| AocClientError::GetLeaderboardError)?;
         let response: PrivateLeaderboardResponse = serde_json::from_str(&body)
-            .with_context(|| format!("Cannot parse the server response as a JSON that maps to PrivateLeaderboardResponse. Got response:\n{}...", &body[..200]))?;
-        Ok(response.into())
+            .with_context(|| format!("Cannot parse the server response as a JSON that maps to PrivateLeaderboardResponse. Got response:\n{}...", body.chars().take(200).collect::<String>()))?;
+        Ok(response.try_into()?)
     }
 }
 
@@ -54,6 +71,17 @@ mod tests {
 
         assert!(leaderboard.entries.first().unwrap().user == String::from("Konrad Pagacz"));
         assert!(leaderboard.entries.last().unwrap().user == String::from("Anna Pytel"));
-        assert!(leaderboard.entries.first().unwrap().points == 187)
+        assert!(leaderboard.entries.first().unwrap().points == 187);
+        assert_eq!(leaderboard.event.year, 2022);
+        assert!(leaderboard
+            .entries
+            .iter()
+            .all(|entry| entry.stars.len() == 25));
+    }
+
+    #[test]
+    fn rejects_a_short_body_that_is_not_a_leaderboard() {
+        let error = PrivateLeaderboard::from_json("<html>Log in</html>".as_bytes()).unwrap_err();
+        assert!(format!("{:#}", error).contains("<html>Log in</html>"));
     }
 }
@@ -1,7 +1,17 @@
 use anyhow::Context;
 
 use super::AOC_URL;
-use crate::domain::{ports::get_stars::GetStars, solved_parts::SolvedParts, stars::Stars};
+use std::collections::HashMap;
+
+use crate::domain::{
+    ports::get_stars::GetStars,
+    solved_parts::SolvedParts,
+    stars::{CalendarCell, CalendarRow, Colour, Stars},
+};
+use crate::infrastructure::{
+    page_cache::{CachedPage, FilePageCache},
+    throttle::EndpointClass,
+};
 
 use super::AocApi;
 
@@ -9,13 +19,20 @@ impl GetStars for AocApi {
     fn get_stars(&self, year: i32) -> anyhow::Result<Stars> {
         let url = reqwest::Url::parse(&format!("{}/{}", AOC_URL, year))
             .expect("Creating a static URL should not fail");
-        Stars::from_readable(
-            self.http_client
-                .get(url)
-                .send()?
-                .error_for_status()
-                .context("GET request for the stars page failed")?,
-        )
+        let body = self
+            .send(EndpointClass::Page, self.http_client.get(url))?
+            .error_for_status()
+            .context("GET request for the stars page failed")?
+            .text()
+            .context("Reading the stars page response body to string failed")?;
+        // Only a page with the calendar is cached, not a login page or an error
+        let stars = Stars::from_readable(body.as_bytes())?;
+        anyhow::ensure!(
+            !stars.calendar.is_empty(),
+            "The stars page has no calendar. Check if your session token is valid"
+        );
+        FilePageCache::save(&CachedPage::Stars { year }, &body)?;
+        Ok(stars)
     }
 }
 
@@ -94,7 +111,101 @@ fn parse_http_response(calendar_http_body: String) -> anyhow::Result<Stars> {
             })
         })
         .collect::<anyhow::Result<Vec<String>>>()?;
-    Ok(Stars::new(solved_statuses, ascii_art))
+    let calendar = parse_calendar(&document, &parse_palette(&calendar_http_body));
+    Ok(Stars::new(solved_statuses, ascii_art, calendar))
+}
+
+/// Reads the colours of the calendar classes from the style sheet of the page
+fn parse_palette(calendar_http_body: &str) -> HashMap<String, Colour> {
+    let colour_regex =
+        regex::Regex::new(r"\.calendar \.([\w-]+)\s*\{\s*color:\s*(#[0-9a-fA-F]{6})")
+            .expect("Parsing a static regex should not fail");
+    colour_regex
+        .captures_iter(calendar_http_body)
+        .filter_map(|captures| Some((captures[1].to_owned(), Colour::from_hex(&captures[2])?)))
+        .collect()
+}
+
+/// Splits the calendar into rows of characters coloured like on the page
+///
+/// The stars of the days are left out, the rows of the days know which
+/// parts are solved instead.
+fn parse_calendar(document: &scraper::Html, palette: &HashMap<String, Colour>) -> Vec<CalendarRow> {
+    let calendar_selector = scraper::Selector::parse("pre.calendar")
+        .expect("Parsing a static CSS selector should not fail");
+    let Some(calendar) = document.select(&calendar_selector).next() else {
+        return Vec::new();
+    };
+    let new_row = || CalendarRow {
+        day: None,
+        solved: SolvedParts::None,
+        cells: Vec::new(),
+    };
+    let mut rows = vec![new_row()];
+    for node in calendar.descendants() {
+        let scraper::Node::Text(text) = node.value() else {
+            continue;
+        };
+        let ancestors = node
+            .ancestors()
+            .filter_map(|ancestor| ancestor.value().as_element())
+            .take_while(|element| !element.classes().any(|class| class == "calendar"))
+            .collect::<Vec<_>>();
+        let is_hidden = ancestors.iter().any(|element| {
+            element.name() == "script"
+                || element
+                    .classes()
+                    .any(|class| class.starts_with("calendar-mark-"))
+        });
+        if is_hidden {
+            continue;
+        }
+        let colour = ancestors.iter().find_map(|element| {
+            element
+                .classes()
+                .find_map(|class| palette.get(class).copied())
+        });
+        let is_day_number = ancestors
+            .iter()
+            .any(|element| element.classes().any(|class| class == "calendar-day"));
+        let day = ancestors.iter().find_map(|element| {
+            let day = element.classes().find_map(|class| {
+                class
+                    .strip_prefix("calendar-day")
+                    .and_then(|day| day.parse::<usize>().ok())
+            })?;
+            let solved = match (
+                element.classes().any(|class| class == "calendar-complete"),
+                element
+                    .classes()
+                    .any(|class| class == "calendar-verycomplete"),
+            ) {
+                (false, false) => SolvedParts::None,
+                (true, false) => SolvedParts::One,
+                (_, _) => SolvedParts::Both,
+            };
+            Some((day, solved))
+        });
+
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                rows.push(new_row());
+            }
+            let row = rows.last_mut().expect("There is always a row");
+            if let Some((day, solved)) = day.filter(|_| is_day_number) {
+                row.day = Some(day);
+                row.solved = solved;
+            }
+            row.cells.extend(
+                line.chars()
+                    .map(|character| CalendarCell { character, colour }),
+            );
+        }
+    }
+    while rows.last().is_some_and(|row| row.cells.is_empty()) {
+        rows.pop();
+    }
+    rows
 }
 
 #[cfg(test)]
@@ -214,6 +325,31 @@ mod tests {
             .all(|&solved_status| solved_status == SolvedParts::Both));
     }
 
+    #[test]
+    fn colours_the_calendar_like_the_page() {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("tests/resources/stars-page-full-stars.html");
+        let stars = Stars::from_readable(BufReader::new(File::open(d).unwrap())).unwrap();
+
+        assert_eq!(stars.calendar.len(), 27);
+        let day_24 = &stars.calendar[3];
+        assert_eq!(day_24.day, Some(24));
+        assert_eq!(day_24.solved, SolvedParts::Both);
+        assert_eq!(day_24.cells[0].character, '/');
+        assert_eq!(day_24.cells[0].colour, Colour::from_hex("#aaaaaa"));
+        assert_eq!(day_24.cells[1].colour, Colour::from_hex("#4d8b03"));
+        let art = day_24
+            .cells
+            .iter()
+            .map(|cell| cell.character)
+            .collect::<String>();
+        assert_eq!(
+            art.trim_end(),
+            stars.pattern[1].trim_end().trim_end_matches('*').trim_end()
+        );
+        assert!(stars.calendar[..2].iter().all(|row| row.day.is_none()));
+    }
+
     #[test]
     fn testing_stars() {
         let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -0,0 +1,369 @@
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use super::{cli_display::CliDisplay, configuration::Configuration};
+
+#[derive(Error, Debug)]
+pub enum CacheInventoryError {
+    #[error("Failed to read the cache directory: {0}")]
+    Read(String),
+
+    #[error("Failed to remove the cache entry: {0}")]
+    Remove(String),
+
+    #[error("Failed to export the cache: {0}")]
+    Export(String),
+
+    #[error("Failed to import the cache: {0}")]
+    Import(String),
+}
+
+/// The kinds of files `elv` keeps in its cache directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum CacheKind {
+    Input,
+    Description,
+    Submissions,
+    Stars,
+    PrivateLeaderboard,
+}
+
+impl CacheKind {
+    pub const ALL: [CacheKind; 5] = [
+        CacheKind::Input,
+        CacheKind::Description,
+        CacheKind::Submissions,
+        CacheKind::Stars,
+        CacheKind::PrivateLeaderboard,
+    ];
+
+    fn directory(&self) -> &'static str {
+        match self {
+            CacheKind::Input => "inputs",
+            CacheKind::Description => "descriptions",
+            CacheKind::Submissions => "submissions",
+            CacheKind::Stars => "stars",
+            CacheKind::PrivateLeaderboard => "private-leaderboards",
+        }
+    }
+
+    fn file_name_regex(&self) -> regex::Regex {
+        regex::Regex::new(match self {
+            CacheKind::Input => r"^input-(\d+)-(\d+)$",
+            CacheKind::Description => r"^description-(\d+)-(\d+)\.html$",
+            CacheKind::Submissions => r"^(\d+)-(\d+)(?:\.json)?$",
+            CacheKind::Stars => r"^stars-(\d+)\.html$",
+            CacheKind::PrivateLeaderboard => r"^private-leaderboard-(\d+)-(.+)\.json$",
+        })
+        .expect("Parsing a static regex should not fail")
+    }
+
+    fn parse_file_name(&self, file_name: &str) -> Option<(usize, Option<usize>, Option<String>)> {
+        let captures = self.file_name_regex().captures(file_name)?;
+        let year = captures.get(1)?.as_str().parse().ok()?;
+        match self {
+            CacheKind::Input | CacheKind::Description | CacheKind::Submissions => {
+                Some((year, Some(captures.get(2)?.as_str().parse().ok()?), None))
+            }
+            CacheKind::Stars => Some((year, None, None)),
+            CacheKind::PrivateLeaderboard => {
+                Some((year, None, Some(captures.get(2)?.as_str().to_owned())))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for CacheKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CacheKind::Input => write!(f, "input"),
+            CacheKind::Description => write!(f, "description"),
+            CacheKind::Submissions => write!(f, "submissions"),
+            CacheKind::Stars => write!(f, "stars"),
+            CacheKind::PrivateLeaderboard => write!(f, "private-leaderboard"),
+        }
+    }
+}
+
+/// A single file in the cache directory
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub kind: CacheKind,
+    pub year: usize,
+    pub day: Option<usize>,
+    pub leaderboard_id: Option<String>,
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: chrono::DateTime<chrono::Utc>,
+}
+
+impl CacheEntry {
+    pub fn age(&self, now: &chrono::DateTime<chrono::Utc>) -> chrono::Duration {
+        *now - self.modified
+    }
+}
+
+/// Selects the cache entries of given kinds, year and day
+///
+/// An empty `kinds` vector matches entries of every kind.
+#[derive(Debug, Clone, Default)]
+pub struct CacheFilter {
+    pub kinds: Vec<CacheKind>,
+    pub year: Option<usize>,
+    pub day: Option<usize>,
+    pub leaderboard_id: Option<String>,
+}
+
+impl CacheFilter {
+    pub fn matches(&self, entry: &CacheEntry) -> bool {
+        (self.kinds.is_empty() || self.kinds.contains(&entry.kind))
+            && self.year.is_none_or(|year| entry.year == year)
+            && self.day.is_none_or(|day| entry.day == Some(day))
+            && self
+                .leaderboard_id
+                .as_ref()
+                .is_none_or(|id| entry.leaderboard_id.as_ref() == Some(id))
+    }
+}
+
+pub struct CacheInventory;
+
+impl CacheInventory {
+    /// Lists the entries in the cache matching the filter, sorted by year, day and kind
+    pub fn entries(filter: &CacheFilter) -> Result<Vec<CacheEntry>, CacheInventoryError> {
+        let mut entries = Vec::new();
+        for kind in CacheKind::ALL {
+            let directory = Self::cache_dir().join(kind.directory());
+            if !directory.exists() {
+                continue;
+            }
+            let read_dir = std::fs::read_dir(&directory)
+                .map_err(|_| CacheInventoryError::Read(directory.display().to_string()))?;
+            for dir_entry in read_dir.flatten() {
+                let file_name = dir_entry.file_name();
+                let (year, day, leaderboard_id) =
+                    match kind.parse_file_name(&file_name.to_string_lossy()) {
+                        Some(parsed) => parsed,
+                        None => continue,
+                    };
+                let metadata = dir_entry.metadata().map_err(|_| {
+                    CacheInventoryError::Read(dir_entry.path().display().to_string())
+                })?;
+                let entry = CacheEntry {
+                    kind,
+                    year,
+                    day,
+                    leaderboard_id,
+                    path: dir_entry.path(),
+                    size: metadata.len(),
+                    modified: metadata
+                        .modified()
+                        .map(chrono::DateTime::<chrono::Utc>::from)
+                        .unwrap_or_else(|_| chrono::Utc::now()),
+                };
+                if filter.matches(&entry) {
+                    entries.push(entry);
+                }
+            }
+        }
+        entries.sort_by(|first, second| {
+            (first.year, first.day, first.kind, &first.leaderboard_id).cmp(&(
+                second.year,
+                second.day,
+                second.kind,
+                &second.leaderboard_id,
+            ))
+        });
+        Ok(entries)
+    }
+
+    pub fn remove(entries: &[CacheEntry]) -> Result<(), CacheInventoryError> {
+        for entry in entries {
+            std::fs::remove_file(&entry.path)
+                .map_err(|_| CacheInventoryError::Remove(entry.path.display().to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the whole cache directory into a gzipped tarball
+    pub fn export(archive_path: &Path) -> Result<(), CacheInventoryError> {
+        let archive = std::fs::File::create(archive_path).map_err(|e| {
+            CacheInventoryError::Export(format!("{}: {}", archive_path.display(), e))
+        })?;
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            archive,
+            flate2::Compression::default(),
+        ));
+        for kind in CacheKind::ALL {
+            let directory = Self::cache_dir().join(kind.directory());
+            if directory.exists() {
+                builder
+                    .append_dir_all(kind.directory(), &directory)
+                    .map_err(|e| CacheInventoryError::Export(e.to_string()))?;
+            }
+        }
+        builder
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .map_err(|e| CacheInventoryError::Export(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Unpacks a tarball created by [`CacheInventory::export`] into the cache directory
+    ///
+    /// Files already in the cache are overwritten by the ones from the archive.
+    /// Returns the number of imported files.
+    pub fn import(archive_path: &Path) -> Result<usize, CacheInventoryError> {
+        let archive = std::fs::File::open(archive_path).map_err(|e| {
+            CacheInventoryError::Import(format!("{}: {}", archive_path.display(), e))
+        })?;
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+        let cache_dir = Self::cache_dir();
+        std::fs::create_dir_all(&cache_dir)
+            .map_err(|e| CacheInventoryError::Import(e.to_string()))?;
+
+        let mut imported = 0;
+        for entry in archive
+            .entries()
+            .map_err(|e| CacheInventoryError::Import(e.to_string()))?
+        {
+            let mut entry = entry.map_err(|e| CacheInventoryError::Import(e.to_string()))?;
+            let path = entry
+                .path()
+                .map_err(|e| CacheInventoryError::Import(e.to_string()))?
+                .into_owned();
+            let is_known_directory = path.components().next().is_some_and(|component| {
+                CacheKind::ALL
+                    .iter()
+                    .any(|kind| component.as_os_str() == kind.directory())
+            });
+            if !is_known_directory {
+                continue;
+            }
+            if entry
+                .unpack_in(&cache_dir)
+                .map_err(|e| CacheInventoryError::Import(e.to_string()))?
+                && entry.header().entry_type().is_file()
+            {
+                imported += 1;
+            }
+        }
+        Ok(imported)
+    }
+
+    fn cache_dir() -> PathBuf {
+        Configuration::get_project_directories()
+            .cache_dir()
+            .to_path_buf()
+    }
+}
+
+impl CliDisplay for Vec<CacheEntry> {
+    fn cli_fmt(&self, _configuration: &Configuration) -> String {
+        fn size(bytes: u64) -> String {
+            if bytes < 1024 {
+                format!("{} B", bytes)
+            } else if bytes < 1024 * 1024 {
+                format!("{:.1} KiB", bytes as f64 / 1024.0)
+            } else {
+                format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+            }
+        }
+        fn age(age: chrono::Duration) -> String {
+            if age.num_days() > 0 {
+                format!("{} days", age.num_days())
+            } else if age.num_hours() > 0 {
+                format!("{} hours", age.num_hours())
+            } else if age.num_minutes() > 0 {
+                format!("{} minutes", age.num_minutes())
+            } else {
+                "just now".to_owned()
+            }
+        }
+
+        let now = chrono::Utc::now();
+        let mut lines = vec![format!(
+            "{:<20} {:>4} {:>3} {:>10} {:>12}",
+            "KIND", "YEAR", "DAY", "SIZE", "AGE"
+        )];
+        lines.extend(self.iter().map(|entry| {
+            let kind = match &entry.leaderboard_id {
+                Some(id) => format!("{} {}", entry.kind, id),
+                None => entry.kind.to_string(),
+            };
+            format!(
+                "{:<20} {:>4} {:>3} {:>10} {:>12}",
+                kind,
+                entry.year,
+                entry
+                    .day
+                    .map_or("-".to_owned(), |day| format!("{:02}", day)),
+                size(entry.size),
+                age(entry.age(&now))
+            )
+        }));
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_names_of_the_cache_files() {
+        assert_eq!(
+            CacheKind::Input.parse_file_name("input-2022-01"),
+            Some((2022, Some(1), None))
+        );
+        assert_eq!(
+            CacheKind::Description.parse_file_name("description-2021-25.html"),
+            Some((2021, Some(25), None))
+        );
+        assert_eq!(
+            CacheKind::Submissions.parse_file_name("2020-7"),
+            Some((2020, Some(7), None))
+        );
+        assert_eq!(
+            CacheKind::Stars.parse_file_name("stars-2019.html"),
+            Some((2019, None, None))
+        );
+        assert_eq!(
+            CacheKind::PrivateLeaderboard.parse_file_name("private-leaderboard-2022-12345.json"),
+            Some((2022, None, Some("12345".to_owned())))
+        );
+        assert_eq!(CacheKind::Input.parse_file_name(".DS_Store"), None);
+    }
+
+    #[test]
+    fn filter_matches_entries() {
+        let entry = CacheEntry {
+            kind: CacheKind::Input,
+            year: 2022,
+            day: Some(1),
+            leaderboard_id: None,
+            path: PathBuf::from("input-2022-01"),
+            size: 10,
+            modified: chrono::Utc::now(),
+        };
+        assert!(CacheFilter::default().matches(&entry));
+        assert!(CacheFilter {
+            kinds: vec![CacheKind::Input, CacheKind::Description],
+            year: Some(2022),
+            day: Some(1),
+            leaderboard_id: None,
+        }
+        .matches(&entry));
+        assert!(!CacheFilter {
+            kinds: vec![CacheKind::Submissions],
+            ..Default::default()
+        }
+        .matches(&entry));
+        assert!(!CacheFilter {
+            day: Some(2),
+            ..Default::default()
+        }
+        .matches(&entry));
+    }
+}
@@ -0,0 +1,205 @@
+use crate::domain::{
+    solved_parts::SolvedParts,
+    stars::{CalendarRow, Colour, Stars, GOLD, SILVER},
+};
+
+/// The background of the Advent of Code website
+const BACKGROUND: Colour = Colour::new(0x0f, 0x0f, 0x23);
+
+/// The colour of the parts of the calendar the page does not colour
+const DEFAULT_COLOUR: Colour = Colour::new(0x66, 0x66, 0x66);
+
+const FONT_SIZE: f32 = 16.0;
+/// The width of a character of a monospace font, relative to the font size
+const CHARACTER_WIDTH: f32 = 0.6;
+const LINE_HEIGHT: f32 = 20.0;
+const MARGIN: f32 = 16.0;
+
+/// The formats the calendar can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImageFormat {
+    /// A scalable vector graphics image
+    Svg,
+    /// A web page
+    Html,
+}
+
+/// Draws the calendar of the year as a standalone image
+pub fn render(stars: &Stars, year: i32, format: ImageFormat) -> String {
+    match format {
+        ImageFormat::Svg => render_svg(stars, year),
+        ImageFormat::Html => render_html(stars, year),
+    }
+}
+
+fn render_svg(stars: &Stars, year: i32) -> String {
+    let rows = stars.calendar.iter().map(runs).collect::<Vec<_>>();
+    let columns = rows
+        .iter()
+        .map(|runs| runs.iter().map(|(text, _)| text.chars().count()).sum())
+        .max()
+        .unwrap_or(0);
+    let width = (2.0 * MARGIN + columns as f32 * FONT_SIZE * CHARACTER_WIDTH).ceil();
+    let height = (2.0 * MARGIN + rows.len() as f32 * LINE_HEIGHT).ceil();
+
+    let lines = rows
+        .iter()
+        .enumerate()
+        .map(|(index, runs)| {
+            let spans = runs
+                .iter()
+                .map(|(text, colour)| {
+                    format!(
+                        r#"<tspan fill="{}">{}</tspan>"#,
+                        colour.to_hex(),
+                        escape(text)
+                    )
+                })
+                .collect::<String>();
+            format!(
+                r#"  <text x="{}" y="{}">{}</text>"#,
+                MARGIN,
+                MARGIN + (index + 1) as f32 * LINE_HEIGHT - LINE_HEIGHT / 4.0,
+                spans
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">
+  <title>Advent of Code {year}</title>
+  <rect width="100%" height="100%" fill="{background}"/>
+  <g font-family="'Source Code Pro', monospace" font-size="{font_size}" xml:space="preserve">
+{lines}
+  </g>
+</svg>
+"#,
+        background = BACKGROUND.to_hex(),
+        font_size = FONT_SIZE,
+    )
+}
+
+fn render_html(stars: &Stars, year: i32) -> String {
+    let lines = stars
+        .calendar
+        .iter()
+        .map(|row| {
+            runs(row)
+                .iter()
+                .map(|(text, colour)| {
+                    format!(
+                        r#"<span style="color:{}">{}</span>"#,
+                        colour.to_hex(),
+                        escape(text)
+                    )
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code {year}</title>
+<style>
+body {{ background: {background}; margin: 0; padding: {margin}px; }}
+pre {{ font-family: 'Source Code Pro', monospace; font-size: {font_size}px; line-height: {line_height}px; margin: 0; }}
+</style>
+</head>
+<body>
+<pre>
+{lines}
+</pre>
+</body>
+</html>
+"#,
+        background = BACKGROUND.to_hex(),
+        margin = MARGIN,
+        font_size = FONT_SIZE,
+        line_height = LINE_HEIGHT,
+    )
+}
+
+/// Splits the row into pieces of text of the same colour, followed by
+/// the stars of the day
+fn runs(row: &CalendarRow) -> Vec<(String, Colour)> {
+    let mut runs = row
+        .cells
+        .chunk_by(|first, second| first.colour == second.colour)
+        .map(|run| {
+            (
+                run.iter().map(|cell| cell.character).collect::<String>(),
+                run[0].colour.unwrap_or(DEFAULT_COLOUR),
+            )
+        })
+        .collect::<Vec<_>>();
+    match (row.day, row.solved) {
+        (Some(_), SolvedParts::Both) => runs.push(("**".to_owned(), GOLD)),
+        (Some(_), SolvedParts::One) => runs.push(("*".to_owned(), SILVER)),
+        _ => (),
+    }
+    runs
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::stars::CalendarCell;
+
+    fn stars() -> Stars {
+        let cells = |art: &str, colour| {
+            art.chars()
+                .map(|character| CalendarCell { character, colour })
+                .collect()
+        };
+        Stars::new(
+            vec![SolvedParts::Both, SolvedParts::One],
+            Vec::new(),
+            vec![
+                CalendarRow {
+                    day: Some(2),
+                    solved: SolvedParts::One,
+                    cells: cells("<@>  2 ", Some(Colour::new(0x7f, 0xbd, 0x39))),
+                },
+                CalendarRow {
+                    day: Some(1),
+                    solved: SolvedParts::Both,
+                    cells: cells("~&~  1 ", None),
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn exports_the_calendar_as_svg() {
+        let svg = render(&stars(), 2022, ImageFormat::Svg);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("<title>Advent of Code 2022</title>"));
+        assert!(svg.contains(
+            r##"<tspan fill="#7fbd39">&lt;@&gt;  2 </tspan><tspan fill="#9999cc">*</tspan>"##
+        ));
+        assert!(svg.contains(
+            r##"<tspan fill="#666666">~&amp;~  1 </tspan><tspan fill="#ffff66">**</tspan>"##
+        ));
+    }
+
+    #[test]
+    fn exports_the_calendar_as_html() {
+        let html = render(&stars(), 2022, ImageFormat::Html);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(r##"<span style="color:#7fbd39">&lt;@&gt;  2 </span><span style="color:#9999cc">*</span>"##));
+    }
+}
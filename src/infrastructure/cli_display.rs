@@ -2,8 +2,10 @@ use colored::Colorize;
 
 use crate::{
     domain::{
-        leaderboard::Leaderboard, private_leaderboard::PrivateLeaderboard,
+        leaderboard::Leaderboard,
+        private_leaderboard::PrivateLeaderboard,
         solved_parts::SolvedParts,
+        stars::{CalendarCell, Stars, GOLD, SILVER},
     },
     Configuration,
 };
@@ -12,6 +14,11 @@ pub trait CliDisplay {
     fn cli_fmt(&self, configuration: &Configuration) -> String;
 }
 
+/// Returns true if the terminal the output is printed to shows colours
+fn is_coloured() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
 impl CliDisplay for Leaderboard {
     fn cli_fmt(&self, _configuration: &Configuration) -> String {
         self.entries
@@ -24,43 +31,152 @@ impl CliDisplay for Leaderboard {
 
 impl CliDisplay for PrivateLeaderboard {
     fn cli_fmt(&self, _configuration: &Configuration) -> String {
-        fn stars(solved_status: &[SolvedParts]) -> String {
-            solved_status
-                .iter()
-                .map(|status| match status {
-                    SolvedParts::None => "*".dimmed(),
-                    SolvedParts::One => "*".white(),
-                    SolvedParts::Both => "*".yellow(),
-                })
-                .fold("".to_owned(), |acc, coloured_string| {
-                    format!("{}{}", acc, coloured_string)
-                })
-        }
-        let out = self
-            .entries
+        fmt_private_leaderboard(self, is_coloured())
+    }
+}
+
+/// Lists the members of the leaderboard under the numbers of the days,
+/// with their stars coloured if `colour` is set
+fn fmt_private_leaderboard(leaderboard: &PrivateLeaderboard, colour: bool) -> String {
+    let stars = |solved_status: &[SolvedParts]| -> String {
+        solved_status
             .iter()
-            .enumerate()
-            .map(|(rank, entry)| {
-                format!(
-                    "{}){:>4} {}  {}",
-                    rank + 1,
-                    entry.points,
-                    stars(&entry.stars),
-                    entry.user
-                )
+            .map(|status| match status {
+                _ if !colour => "*".to_owned(),
+                SolvedParts::None => "*".dimmed().to_string(),
+                SolvedParts::One => "*".white().to_string(),
+                SolvedParts::Both => "*".yellow().to_string(),
             })
-            .collect::<Vec<_>>()
-            .join("\n");
-        const FIRST_LINE: &str = "                1111111111222222222";
-        const SECOND_LINE: &str = "       12345678901234567890123456789";
-        format!(
-            "{}\n{}\n{}",
-            &FIRST_LINE[..(7 + self.entries[0].stars.len())],
-            &SECOND_LINE[..(7 + self.entries[0].stars.len())],
-            out
-        )
+            .collect()
+    };
+    let out = leaderboard
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(rank, entry)| {
+            format!(
+                "{}){:>4} {}  {}",
+                rank + 1,
+                entry.points,
+                stars(&entry.stars),
+                entry.user
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let days = 1..=leaderboard.event.days();
+    let tens = days
+        .clone()
+        .map(|day| match day / 10 {
+            0 => " ".to_owned(),
+            tens => tens.to_string(),
+        })
+        .collect::<String>();
+    let ones = days.map(|day| (day % 10).to_string()).collect::<String>();
+    format!("       {}\n       {}\n{}", tens, ones, out)
+}
+
+impl CliDisplay for Stars {
+    fn cli_fmt(&self, _configuration: &Configuration) -> String {
+        fmt_calendar(self, is_coloured())
     }
 }
 
+/// Draws the calendar with the gold stars of the solved riddles and the
+/// silver stars of the half-solved ones, in the colours of the page if
+/// `colour` is set
+fn fmt_calendar(stars: &Stars, colour: bool) -> String {
+    stars
+        .calendar
+        .iter()
+        .map(|row| {
+            let art = row
+                .cells
+                .chunk_by(|first, second| first.colour == second.colour)
+                .map(|run| {
+                    let text = run
+                        .iter()
+                        .map(|cell: &CalendarCell| cell.character)
+                        .collect::<String>();
+                    match run[0].colour {
+                        Some(cell_colour) if colour => text
+                            .truecolor(cell_colour.red, cell_colour.green, cell_colour.blue)
+                            .to_string(),
+                        _ => text,
+                    }
+                })
+                .collect::<String>();
+            let day_stars = match (row.day, row.solved) {
+                (Some(_), SolvedParts::Both) => Some(("**", GOLD)),
+                (Some(_), SolvedParts::One) => Some(("*", SILVER)),
+                _ => None,
+            };
+            let day_stars = match day_stars {
+                Some((text, star_colour)) if colour => text
+                    .truecolor(star_colour.red, star_colour.green, star_colour.blue)
+                    .to_string(),
+                Some((text, _)) => text.to_owned(),
+                None => String::new(),
+            };
+            format!("{}{}", art, day_stars).trim_end().to_owned()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use crate::domain::{
+        event::Event,
+        private_leaderboard::PrivateLeaderboardEntry,
+        stars::{CalendarRow, Colour},
+    };
+
+    #[test]
+    fn numbers_the_days_of_the_event() {
+        let leaderboard = PrivateLeaderboard::new(
+            Event::new(2025).unwrap(),
+            vec![PrivateLeaderboardEntry {
+                user: "Elf".to_owned(),
+                points: 12,
+                stars: vec![SolvedParts::Both; 12],
+            }],
+        );
+
+        assert_eq!(
+            fmt_private_leaderboard(&leaderboard, false),
+            concat!(
+                "                111\n",
+                "       123456789012\n",
+                "1)  12 ************  Elf"
+            )
+        );
+    }
+
+    #[test]
+    fn draws_the_stars_of_the_days_after_the_calendar() {
+        let row = |day, solved, art: &str| CalendarRow {
+            day,
+            solved,
+            cells: art
+                .chars()
+                .map(|character| CalendarCell {
+                    character,
+                    colour: Some(Colour::new(0x7f, 0xbd, 0x39)),
+                })
+                .collect(),
+        };
+        let stars = Stars::new(
+            vec![SolvedParts::One, SolvedParts::None],
+            Vec::new(),
+            vec![
+                row(None, SolvedParts::None, " /\\    "),
+                row(Some(2), SolvedParts::None, "/  \\  2 "),
+                row(Some(1), SolvedParts::One, "@@@@  1 "),
+            ],
+        );
+
+        assert_eq!(fmt_calendar(&stars, false), " /\\\n/  \\  2\n@@@@  1 *");
+    }
+}
@@ -1,15 +1,20 @@
 use config::{Map, Source};
 
+use super::configuration_keys::{self, ConfigurationKey};
+
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct AocConfiguration {
     #[serde(default = "default_token")]
     pub token: String,
+    #[serde(default)]
+    pub offline: bool,
 }
 
 impl Default for AocConfiguration {
     fn default() -> Self {
         AocConfiguration {
             token: default_token(),
+            offline: false,
         }
     }
 }
@@ -20,15 +25,218 @@ fn default_token() -> String {
 
 #[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize)]
 pub struct CliConfiguration {
+    #[serde(default = "default_output_width")]
     pub output_width: u32,
+    #[serde(default)]
+    pub rich_descriptions: bool,
+    #[serde(default)]
+    pub easter_eggs: bool,
 }
 
 impl Default for CliConfiguration {
     fn default() -> Self {
-        CliConfiguration { output_width: 120 }
+        CliConfiguration {
+            output_width: default_output_width(),
+            rich_descriptions: false,
+            easter_eggs: false,
+        }
+    }
+}
+
+fn default_output_width() -> u32 {
+    120
+}
+
+/// The minimal spacing between requests to the Advent of Code servers, in seconds
+///
+/// Each group of endpoints is throttled separately. If a request would have
+/// to wait longer than `max_wait`, it fails instead.
+#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize)]
+pub struct ThrottleConfiguration {
+    #[serde(default = "default_pages_spacing")]
+    pub pages: u64,
+    #[serde(default = "default_inputs_spacing")]
+    pub inputs: u64,
+    #[serde(default = "default_answers_spacing")]
+    pub answers: u64,
+    #[serde(default = "default_private_leaderboards_spacing")]
+    pub private_leaderboards: u64,
+    #[serde(default = "default_max_wait")]
+    pub max_wait: u64,
+}
+
+impl Default for ThrottleConfiguration {
+    fn default() -> Self {
+        ThrottleConfiguration {
+            pages: default_pages_spacing(),
+            inputs: default_inputs_spacing(),
+            answers: default_answers_spacing(),
+            private_leaderboards: default_private_leaderboards_spacing(),
+            max_wait: default_max_wait(),
+        }
+    }
+}
+
+fn default_pages_spacing() -> u64 {
+    1
+}
+
+fn default_inputs_spacing() -> u64 {
+    1
+}
+
+fn default_answers_spacing() -> u64 {
+    5
+}
+
+/// Advent of Code asks to request a private leaderboard at most once every 15 minutes
+fn default_private_leaderboards_spacing() -> u64 {
+    900
+}
+
+fn default_max_wait() -> u64 {
+    10
+}
+
+/// The retries of the requests that failed because of a transient error
+///
+/// Only the requests that can be safely repeated are retried. Answers are never
+/// sent twice. The delay before a retry doubles with each attempt, starting
+/// at `base_delay_ms` and never exceeding `max_delay_ms`.
+#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize)]
+pub struct RetryConfiguration {
+    #[serde(default = "default_retry_attempts")]
+    pub attempts: u32,
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub base_delay_ms: u64,
+    #[serde(default = "default_retry_max_delay_ms")]
+    pub max_delay_ms: u64,
+    #[serde(default = "default_retry_jitter")]
+    pub jitter: bool,
+}
+
+impl Default for RetryConfiguration {
+    fn default() -> Self {
+        RetryConfiguration {
+            attempts: default_retry_attempts(),
+            base_delay_ms: default_retry_base_delay_ms(),
+            max_delay_ms: default_retry_max_delay_ms(),
+            jitter: default_retry_jitter(),
+        }
+    }
+}
+
+fn default_retry_attempts() -> u32 {
+    3
+}
+
+fn default_retry_base_delay_ms() -> u64 {
+    1000
+}
+
+fn default_retry_max_delay_ms() -> u64 {
+    10000
+}
+
+fn default_retry_jitter() -> bool {
+    true
+}
+
+/// The settings of the connection to the Advent of Code servers
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct HttpConfiguration {
+    /// The URL of the proxy used for all the requests
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// The comma-separated list of hosts that bypass the proxy
+    #[serde(default)]
+    pub no_proxy: Option<String>,
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    /// The longest time a whole request may take, from connecting to reading the response
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// The path to a PEM file with extra trusted root certificates
+    #[serde(default)]
+    pub ca_bundle: Option<std::path::PathBuf>,
+    /// The contact appended to the user agent of the requests
+    #[serde(default)]
+    pub user_agent_contact: Option<String>,
+}
+
+impl Default for HttpConfiguration {
+    fn default() -> Self {
+        HttpConfiguration {
+            proxy: None,
+            no_proxy: None,
+            connect_timeout_secs: default_connect_timeout_secs(),
+            request_timeout_secs: default_request_timeout_secs(),
+            ca_bundle: None,
+            user_agent_contact: None,
+        }
     }
 }
 
+fn default_connect_timeout_secs() -> u64 {
+    10
+}
+
+fn default_request_timeout_secs() -> u64 {
+    30
+}
+
+/// How `elv` runs your solutions
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct SolutionsConfiguration {
+    /// The shell command running the solution of a riddle
+    #[serde(default)]
+    pub command: Option<String>,
+    /// The template of the path to the files with your own examples of the riddles
+    #[serde(default = "default_examples_file")]
+    pub examples: String,
+    /// The path to the file with the correct answers to the riddles
+    #[serde(default = "default_answers_file")]
+    pub answers: std::path::PathBuf,
+}
+
+impl Default for SolutionsConfiguration {
+    fn default() -> Self {
+        SolutionsConfiguration {
+            command: None,
+            examples: default_examples_file(),
+            answers: default_answers_file(),
+        }
+    }
+}
+
+fn default_examples_file() -> String {
+    "examples/{year}/day{day:02}.toml".to_owned()
+}
+
+fn default_answers_file() -> std::path::PathBuf {
+    std::path::PathBuf::from("answers.toml")
+}
+
+/// The defaults of a repository with solutions, usually set in its `elv.toml`
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct ProjectConfiguration {
+    /// The year of the riddles used when no year is given
+    #[serde(default)]
+    pub year: Option<usize>,
+    /// The template of the path to the downloaded inputs
+    #[serde(default)]
+    pub input: Option<String>,
+    /// The template of the path to the downloaded descriptions
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The IDs of the private leaderboards used when no ID is given
+    #[serde(default)]
+    pub leaderboards: Vec<String>,
+}
+
+/// The name of the configuration file of a repository with solutions
+pub const PROJECT_CONFIGURATION_FILE: &str = "elv.toml";
+
 #[derive(thiserror::Error, Debug)]
 pub enum ConfigurationError {
     #[error("Cannot create a configuration file")]
@@ -39,13 +247,36 @@ pub enum ConfigurationError {
     UpdateError,
     #[error("Cannot build the configuration")]
     BuildError(#[from] config::ConfigError),
+    #[error("Cannot read the configuration file: {0}")]
+    ParseError(#[from] toml::de::Error),
+    #[error(
+        "Unknown configuration key `{key}`.{hint} See `elv config keys` for the supported keys"
+    )]
+    UnknownKey { key: String, hint: String },
+    #[error("`{value}` is not a valid value of `{key}`. Expected {expected}")]
+    InvalidValue {
+        key: String,
+        value: String,
+        expected: &'static str,
+    },
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize, Default)]
 pub struct Configuration {
     #[serde(default)]
     pub aoc: AocConfiguration,
+    #[serde(default)]
     pub cli: CliConfiguration,
+    #[serde(default)]
+    pub throttle: ThrottleConfiguration,
+    #[serde(default)]
+    pub retry: RetryConfiguration,
+    #[serde(default)]
+    pub http: HttpConfiguration,
+    #[serde(default)]
+    pub solutions: SolutionsConfiguration,
+    #[serde(default)]
+    pub project: ProjectConfiguration,
 }
 
 impl Configuration {
@@ -53,22 +284,59 @@ impl Configuration {
         match Self::builder().build() {
             Ok(config) => config
                 .try_deserialize::<Configuration>()
+                .map(Configuration::in_project)
                 .unwrap_or_default(),
             Err(_) => Configuration::default(),
         }
     }
 
+    /// Makes the relative paths of the configuration relative to the directory
+    /// of the `elv.toml`, if there is one
+    ///
+    /// Otherwise the paths would follow the working directory, and the files
+    /// of a repository would be scattered over its subdirectories.
+    pub fn in_project(self) -> Self {
+        match Self::find_project_file()
+            .as_deref()
+            .and_then(std::path::Path::parent)
+        {
+            Some(project_directory) => self.with_paths_relative_to(project_directory),
+            None => self,
+        }
+    }
+
+    fn with_paths_relative_to(mut self, directory: &std::path::Path) -> Self {
+        let resolve = |template: &str| directory.join(template).to_string_lossy().into_owned();
+        self.project.input = self.project.input.as_deref().map(resolve);
+        self.project.description = self.project.description.as_deref().map(resolve);
+        self.solutions.examples = resolve(&self.solutions.examples);
+        self.solutions.answers = directory.join(&self.solutions.answers);
+        self
+    }
+
     pub fn builder() -> config::ConfigBuilder<config::builder::DefaultState> {
         let config_builder_from_file = match Self::builder_from_config_file() {
             Ok(builder) => builder,
             Err(_) => return config::ConfigBuilder::default(),
         };
 
-        config_builder_from_file.add_source(
-            config::Environment::with_prefix("AOC")
-                .separator("_")
-                .keep_prefix(true),
-        )
+        let config_builder_from_file = match Self::find_project_file() {
+            Some(project_file) => config_builder_from_file
+                .add_source(config::File::from(project_file).format(config::FileFormat::Toml)),
+            None => config_builder_from_file,
+        };
+
+        config_builder_from_file
+            .add_source(
+                config::Environment::with_prefix("AOC")
+                    .separator("_")
+                    .keep_prefix(true),
+            )
+            .add_source(
+                config::Environment::with_prefix("ELV")
+                    .prefix_separator("_")
+                    .separator("__"),
+            )
     }
 
     pub fn get_project_directories() -> directories::ProjectDirs {
@@ -76,6 +344,19 @@ impl Configuration {
             .expect("Failed to get the project directories")
     }
 
+    /// Finds the `elv.toml` in the working directory or the closest of its parents
+    pub fn find_project_file() -> Option<std::path::PathBuf> {
+        let working_directory = std::env::current_dir().ok()?;
+        Self::find_project_file_from(&working_directory)
+    }
+
+    fn find_project_file_from(directory: &std::path::Path) -> Option<std::path::PathBuf> {
+        directory
+            .ancestors()
+            .map(|ancestor| ancestor.join(PROJECT_CONFIGURATION_FILE))
+            .find(|path| path.is_file())
+    }
+
     fn builder_from_config_file(
     ) -> Result<config::ConfigBuilder<config::builder::DefaultState>, ConfigurationError> {
         let project_dirs = Self::get_project_directories();
@@ -83,10 +364,35 @@ impl Configuration {
             Self::write_default_config()?;
         }
 
-        Ok(config::Config::builder().add_source(
-            config::File::with_name(project_dirs.config_dir().join(".config").to_str().unwrap())
+        let contents = std::fs::read_to_string(Self::file_path()).unwrap_or_default();
+        Ok(
+            Self::with_renamed_keys(config::Config::builder(), &contents)?.add_source(
+                config::File::with_name(
+                    project_dirs.config_dir().join(".config").to_str().unwrap(),
+                )
                 .format(config::FileFormat::Toml),
-        ))
+            ),
+        )
+    }
+
+    /// Reads the values of the renamed keys from the configuration file written
+    /// by the older versions of `elv`
+    ///
+    /// The values become the defaults of the new keys, so they give way to
+    /// the new keys set anywhere.
+    fn with_renamed_keys(
+        builder: config::ConfigBuilder<config::builder::DefaultState>,
+        contents: &str,
+    ) -> Result<config::ConfigBuilder<config::builder::DefaultState>, ConfigurationError> {
+        let read_timeout_secs = toml::from_str::<toml::Value>(contents)
+            .ok()
+            .and_then(|file| file.get("http")?.get("read_timeout_secs")?.as_integer());
+        Ok(match read_timeout_secs {
+            Some(read_timeout_secs) => {
+                builder.set_default("http.request_timeout_secs", read_timeout_secs)?
+            }
+            None => builder,
+        })
     }
 
     pub fn get_file_configuration_map() -> Result<Map<String, config::Value>, ConfigurationError> {
@@ -101,6 +407,99 @@ impl Configuration {
         .collect()?)
     }
 
+    /// Returns the path to the configuration file
+    pub fn file_path() -> std::path::PathBuf {
+        Self::get_project_directories().config_dir().join(".config")
+    }
+
+    /// Finds the documentation of the key, or fails with a hint if the key is not supported
+    pub fn validate_key(key: &str) -> Result<&'static ConfigurationKey, ConfigurationError> {
+        configuration_keys::find_key(key).ok_or_else(|| ConfigurationError::UnknownKey {
+            key: key.to_owned(),
+            hint: configuration_keys::suggest_key(key)
+                .map(|suggestion| format!(" Did you mean `{}`?", suggestion))
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Returns the value of the key in use, or `None` if the key is not set
+    ///
+    /// The value comes from the configuration file, `elv.toml` or the environment.
+    pub fn get_configuration_key(key: &str) -> Result<Option<String>, ConfigurationError> {
+        Self::validate_key(key)?;
+        let configuration = toml::Value::try_from(Self::new())?;
+        let value = key
+            .split('.')
+            .try_fold(&configuration, |value, name| value.get(name));
+        Ok(value.map(|value| match value {
+            toml::Value::String(text) => text.clone(),
+            other => other.to_string(),
+        }))
+    }
+
+    /// Checks the key and the type of the value, then writes it to the configuration file
+    pub fn set_configuration_key(key: &str, value: &str) -> Result<(), ConfigurationError> {
+        let documented = Self::validate_key(key)?;
+        let parsed =
+            documented
+                .value_type
+                .parse(value)
+                .ok_or_else(|| ConfigurationError::InvalidValue {
+                    key: key.to_owned(),
+                    value: value.to_owned(),
+                    expected: documented.value_type.expected(),
+                })?;
+        Self::update_configuration_key(key, parsed)
+    }
+
+    /// Removes the key from the configuration file, so its default value is used
+    pub fn unset_configuration_key(key: &str) -> Result<(), ConfigurationError> {
+        Self::validate_key(key)?;
+        let contents = std::fs::read_to_string(Self::file_path()).unwrap_or_default();
+        let mut file: toml::value::Table = toml::from_str(&contents)?;
+        if let Some((section, name)) = key.split_once('.') {
+            if let Some(toml::Value::Table(values)) = file.get_mut(section) {
+                values.remove(name);
+            }
+        }
+        Self::replace_file_contents(&toml::to_string(&file)?)
+    }
+
+    /// Checks the contents of a configuration file for unknown keys and values of wrong types
+    pub fn validate_file_contents(contents: &str) -> Result<(), ConfigurationError> {
+        let file: toml::value::Table = toml::from_str(contents)?;
+        for (section, values) in &file {
+            let Some(values) = values.as_table() else {
+                Self::validate_key(section)?;
+                continue;
+            };
+            for (name, value) in values {
+                let key = format!("{}.{}", section, name);
+                let documented = Self::validate_key(&key)?;
+                if !documented.value_type.matches(value) {
+                    return Err(ConfigurationError::InvalidValue {
+                        key,
+                        value: value.to_string(),
+                        expected: documented.value_type.expected(),
+                    });
+                }
+            }
+        }
+        toml::from_str::<Configuration>(contents)?;
+        Ok(())
+    }
+
+    /// Validates the contents and replaces the configuration file with them
+    pub fn replace_file_contents(contents: &str) -> Result<(), ConfigurationError> {
+        Self::validate_file_contents(contents)?;
+        let path = Self::file_path();
+        if let Some(config_dir) = path.parent() {
+            std::fs::create_dir_all(config_dir)?;
+        }
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
     pub fn update_configuration_key<T>(key: &str, value: T) -> Result<(), ConfigurationError>
     where
         T: Into<config::Value>,
@@ -148,4 +547,92 @@ mod tests {
         assert!(result.is_ok());
         assert!(config_dir.exists());
     }
+
+    #[test]
+    fn rejects_unknown_keys_and_values_of_wrong_types() {
+        assert!(Configuration::validate_file_contents("[cli]\noutput_width = 80\n").is_ok());
+        assert!(matches!(
+            Configuration::validate_file_contents("[cli]\noutput_witdh = 80\n"),
+            Err(ConfigurationError::UnknownKey { hint, .. }) if hint.contains("cli.output_width")
+        ));
+        assert!(matches!(
+            Configuration::validate_file_contents("[cli]\noutput_width = \"wide\"\n"),
+            Err(ConfigurationError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            Configuration::validate_file_contents("[cli]\noutput_width = 4294967296\n"),
+            Err(ConfigurationError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            Configuration::validate_file_contents("[cli\n"),
+            Err(ConfigurationError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn finds_the_project_file_in_the_parent_directories() {
+        let root = std::env::temp_dir().join("elv-project-file-test");
+        let nested = root.join("2022").join("day01");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(
+            root.join(PROJECT_CONFIGURATION_FILE),
+            "[project]\nyear = 2022\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Configuration::find_project_file_from(&nested),
+            Some(root.join(PROJECT_CONFIGURATION_FILE))
+        );
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn reads_the_old_name_of_the_request_timeout() {
+        let build = |contents: &str, request_timeout_secs: Option<u64>| {
+            Configuration::with_renamed_keys(config::Config::builder(), contents)
+                .unwrap()
+                .add_source(config::File::from_str(contents, config::FileFormat::Toml))
+                .set_override_option("http.request_timeout_secs", request_timeout_secs)
+                .unwrap()
+                .build()
+                .unwrap()
+                .try_deserialize::<Configuration>()
+                .unwrap()
+                .http
+                .request_timeout_secs
+        };
+        assert_eq!(build("[http]\nread_timeout_secs = 5\n", None), 5);
+        assert_eq!(build("[http]\nread_timeout_secs = 5\n", Some(3)), 3);
+        assert_eq!(
+            build(
+                "[http]\nread_timeout_secs = 5\nrequest_timeout_secs = 7\n",
+                None
+            ),
+            7
+        );
+    }
+
+    #[test]
+    fn resolves_the_relative_paths_against_the_project_directory() {
+        let root = std::env::temp_dir().join("elv-project");
+        let mut configuration = Configuration::default();
+        configuration.project.input = Some("inputs/{year}/day{day:02}.txt".to_owned());
+        configuration.project.description = Some("/descriptions/{day}.txt".to_owned());
+
+        let configuration = configuration.with_paths_relative_to(&root);
+        assert_eq!(
+            configuration.project.input.map(std::path::PathBuf::from),
+            Some(root.join("inputs/{year}/day{day:02}.txt"))
+        );
+        assert_eq!(
+            configuration.project.description.as_deref(),
+            Some("/descriptions/{day}.txt")
+        );
+        assert_eq!(
+            std::path::PathBuf::from(configuration.solutions.examples),
+            root.join("examples/{year}/day{day:02}.toml")
+        );
+        assert_eq!(configuration.solutions.answers, root.join("answers.toml"));
+    }
 }
@@ -0,0 +1,362 @@
+/// The type of the value of a configuration key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Text,
+    /// A whole number stored in 64 bits, bounded by the integers of `TOML`
+    Integer,
+    /// A whole number stored in 32 bits
+    SmallInteger,
+    Boolean,
+    Path,
+    /// A list of texts, given as comma-separated values on the command line
+    List,
+}
+
+impl std::fmt::Display for ValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueType::Text => write!(f, "text"),
+            ValueType::Integer | ValueType::SmallInteger => write!(f, "integer"),
+            ValueType::Boolean => write!(f, "boolean"),
+            ValueType::Path => write!(f, "path"),
+            ValueType::List => write!(f, "list"),
+        }
+    }
+}
+
+impl ValueType {
+    /// Converts the value given on the command line to the value of the type
+    pub fn parse(&self, value: &str) -> Option<config::Value> {
+        match self {
+            ValueType::Text | ValueType::Path => Some(value.into()),
+            ValueType::Integer | ValueType::SmallInteger => value
+                .parse::<i64>()
+                .ok()
+                .filter(|number| self.accepts_integer(*number))
+                .map(config::Value::from),
+            ValueType::Boolean => value.parse::<bool>().ok().map(config::Value::from),
+            ValueType::List => Some(config::Value::from(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_owned)
+                    .collect::<Vec<_>>(),
+            )),
+        }
+    }
+
+    /// Returns true if the value read from a `TOML` file has the type
+    pub fn matches(&self, value: &toml::Value) -> bool {
+        match self {
+            ValueType::Text | ValueType::Path => value.is_str(),
+            ValueType::Integer | ValueType::SmallInteger => value
+                .as_integer()
+                .is_some_and(|number| self.accepts_integer(number)),
+            ValueType::Boolean => value.is_bool(),
+            ValueType::List => value
+                .as_array()
+                .is_some_and(|items| items.iter().all(toml::Value::is_str)),
+        }
+    }
+
+    /// Describes the values of the type accepted on the command line
+    pub fn expected(&self) -> &'static str {
+        match self {
+            ValueType::Text => "any text",
+            ValueType::Integer => "a whole number from 0 to 9223372036854775807, e.g. `10`",
+            ValueType::SmallInteger => "a whole number from 0 to 4294967295, e.g. `10`",
+            ValueType::Boolean => "`true` or `false`",
+            ValueType::Path => "a path to a file",
+            ValueType::List => "comma-separated values, e.g. `123,456`",
+        }
+    }
+
+    /// Returns true if the number fits in the field the type is read into
+    fn accepts_integer(&self, number: i64) -> bool {
+        match self {
+            ValueType::SmallInteger => u32::try_from(number).is_ok(),
+            _ => number >= 0,
+        }
+    }
+}
+
+/// A key of the configuration file with its documentation
+#[derive(Debug)]
+pub struct ConfigurationKey {
+    pub key: &'static str,
+    pub value_type: ValueType,
+    /// The default value, or `None` if the key is not set by default
+    pub default: Option<&'static str>,
+    pub description: &'static str,
+}
+
+const fn key(
+    key: &'static str,
+    value_type: ValueType,
+    default: Option<&'static str>,
+    description: &'static str,
+) -> ConfigurationKey {
+    ConfigurationKey {
+        key,
+        value_type,
+        default,
+        description,
+    }
+}
+
+/// Every key supported in the configuration file and `elv.toml`
+pub const CONFIGURATION_KEYS: &[ConfigurationKey] = &[
+    key(
+        "aoc.token",
+        ValueType::Text,
+        Some(""),
+        "The session token used to authenticate you on the Advent of Code servers",
+    ),
+    key(
+        "aoc.offline",
+        ValueType::Boolean,
+        Some("false"),
+        "Serves everything from the cache without connecting to the servers",
+    ),
+    key(
+        "cli.output_width",
+        ValueType::SmallInteger,
+        Some("120"),
+        "The width of the printed descriptions and leaderboards",
+    ),
+    key(
+        "cli.rich_descriptions",
+        ValueType::Boolean,
+        Some("false"),
+        "Colours the descriptions like the riddle page",
+    ),
+    key(
+        "cli.easter_eggs",
+        ValueType::Boolean,
+        Some("false"),
+        "Shows the jokes hidden in the titles of the riddle text",
+    ),
+    key(
+        "throttle.pages",
+        ValueType::Integer,
+        Some("1"),
+        "The minimal spacing between downloads of pages, in seconds",
+    ),
+    key(
+        "throttle.inputs",
+        ValueType::Integer,
+        Some("1"),
+        "The minimal spacing between downloads of inputs, in seconds",
+    ),
+    key(
+        "throttle.answers",
+        ValueType::Integer,
+        Some("5"),
+        "The minimal spacing between submitted answers, in seconds",
+    ),
+    key(
+        "throttle.private_leaderboards",
+        ValueType::Integer,
+        Some("900"),
+        "The minimal spacing between downloads of a private leaderboard, in seconds",
+    ),
+    key(
+        "throttle.max_wait",
+        ValueType::Integer,
+        Some("10"),
+        "The longest wait for a throttled request before it fails, in seconds",
+    ),
+    key(
+        "retry.attempts",
+        ValueType::SmallInteger,
+        Some("3"),
+        "How many times a request failed because of a transient error is retried",
+    ),
+    key(
+        "retry.base_delay_ms",
+        ValueType::Integer,
+        Some("1000"),
+        "The delay before the first retry, in milliseconds",
+    ),
+    key(
+        "retry.max_delay_ms",
+        ValueType::Integer,
+        Some("10000"),
+        "The longest delay before a retry, in milliseconds",
+    ),
+    key(
+        "retry.jitter",
+        ValueType::Boolean,
+        Some("true"),
+        "Randomises the delays before the retries",
+    ),
+    key(
+        "http.proxy",
+        ValueType::Text,
+        None,
+        "The URL of the proxy used for all the requests",
+    ),
+    key(
+        "http.no_proxy",
+        ValueType::Text,
+        None,
+        "The comma-separated list of hosts that bypass the proxy",
+    ),
+    key(
+        "http.connect_timeout_secs",
+        ValueType::Integer,
+        Some("10"),
+        "How long to wait for the connection to the servers, in seconds",
+    ),
+    key(
+        "http.request_timeout_secs",
+        ValueType::Integer,
+        Some("30"),
+        "The longest time a whole request may take, in seconds",
+    ),
+    key(
+        "http.ca_bundle",
+        ValueType::Path,
+        None,
+        "A PEM file with extra trusted root certificates",
+    ),
+    key(
+        "http.user_agent_contact",
+        ValueType::Text,
+        None,
+        "Your contact appended to the user agent of the requests",
+    ),
+    key(
+        "solutions.command",
+        ValueType::Text,
+        None,
+        "The shell command running your solution in `elv test` and `elv verify`",
+    ),
+    key(
+        "solutions.examples",
+        ValueType::Text,
+        Some("examples/{year}/day{day:02}.toml"),
+        "The template of the path to the files with your own examples",
+    ),
+    key(
+        "solutions.answers",
+        ValueType::Path,
+        Some("answers.toml"),
+        "The file with the correct answers to the riddles",
+    ),
+    key(
+        "project.year",
+        ValueType::Integer,
+        None,
+        "The year of the riddles used when you do not give one",
+    ),
+    key(
+        "project.input",
+        ValueType::Text,
+        None,
+        "The template of the path the inputs are written to",
+    ),
+    key(
+        "project.description",
+        ValueType::Text,
+        None,
+        "The template of the path the descriptions are written to",
+    ),
+    key(
+        "project.leaderboards",
+        ValueType::List,
+        Some("[]"),
+        "The IDs of the private leaderboards used when you do not give one",
+    ),
+];
+
+/// Finds the documentation of the key
+pub fn find_key(key: &str) -> Option<&'static ConfigurationKey> {
+    CONFIGURATION_KEYS.iter().find(|known| known.key == key)
+}
+
+/// The keys renamed in the past, with their current names
+const RENAMED_KEYS: &[(&str, &str)] = &[("http.read_timeout_secs", "http.request_timeout_secs")];
+
+/// Finds the supported key most similar to the unknown one
+pub fn suggest_key(key: &str) -> Option<&'static str> {
+    if let Some((_, renamed)) = RENAMED_KEYS.iter().find(|(old, _)| *old == key) {
+        return Some(renamed);
+    }
+    CONFIGURATION_KEYS
+        .iter()
+        .map(|known| (edit_distance(key, known.key), known.key))
+        .filter(|(distance, known)| *distance <= 3 || known.ends_with(&format!(".{}", key)))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+/// The number of single-character edits turning one text into the other
+fn edit_distance(first: &str, second: &str) -> usize {
+    let second: Vec<char> = second.chars().collect();
+    let mut previous: Vec<usize> = (0..=second.len()).collect();
+    for (i, first_char) in first.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, second_char) in second.iter().enumerate() {
+            let substitution = previous[j] + usize::from(first_char != *second_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[second.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Configuration;
+
+    #[test]
+    fn documents_every_key_with_its_default() {
+        let defaults = toml::Value::try_from(Configuration::default()).unwrap();
+        for (section, values) in defaults.as_table().unwrap() {
+            for (name, value) in values.as_table().unwrap() {
+                let key = format!("{}.{}", section, name);
+                let documented =
+                    find_key(&key).unwrap_or_else(|| panic!("{} is undocumented", key));
+                let default = match value {
+                    toml::Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                assert_eq!(documented.default, Some(default.as_str()), "{}", key);
+                assert!(documented.value_type.matches(value), "{}", key);
+            }
+        }
+    }
+
+    #[test]
+    fn suggests_the_similar_keys() {
+        assert_eq!(suggest_key("cli.output_witdh"), Some("cli.output_width"));
+        assert_eq!(suggest_key("token"), Some("aoc.token"));
+        assert_eq!(
+            suggest_key("http.read_timeout_secs"),
+            Some("http.request_timeout_secs")
+        );
+        assert_eq!(suggest_key("something.else"), None);
+    }
+
+    #[test]
+    fn parses_the_values_by_their_type() {
+        assert!(ValueType::Integer.parse("-1").is_none());
+        assert!(ValueType::Integer.parse("9223372036854775808").is_none());
+        assert_eq!(
+            ValueType::Integer
+                .parse("9223372036854775807")
+                .unwrap()
+                .into_int()
+                .unwrap(),
+            i64::MAX
+        );
+        assert!(ValueType::SmallInteger.parse("4294967296").is_none());
+        assert!(ValueType::SmallInteger.parse("4294967295").is_some());
+        assert!(ValueType::Boolean.parse("yes").is_none());
+        let list = ValueType::List.parse("123, 456").unwrap();
+        assert_eq!(list.into_array().unwrap().len(), 2);
+    }
+}
@@ -1,17 +1,31 @@
 use std::collections::HashMap;
 
 use anyhow::{Context, Result};
-use chrono::TimeZone;
 
 use super::{
-    aoc_api::AocApi, cli_display::CliDisplay, configuration::Configuration,
-    find_riddle_part::FindRiddlePart, http_description::HttpDescription,
-    input_cache::FileInputCache, submission_history::SubmissionHistory,
+    answers_file::AnswersFile,
+    aoc_api::AocApi,
+    cache_inventory::{CacheEntry, CacheFilter, CacheInventory, CacheKind},
+    cli_display::CliDisplay,
+    configuration::Configuration,
+    examples_file::ExamplesFile,
+    http_description::HttpDescription,
+    input_cache::FileInputCache,
+    page_cache::{CachedPage, FilePageCache, PageCacheError},
+    path_template::PathTemplate,
+    solution_runner::SolutionRunner,
+    submission_history::SubmissionHistory,
+    throttle::ThrottleError,
 };
 use crate::domain::{
+    answer_check::{check_answer, normalise_answer},
+    day_range::DayRange,
+    description_parts::DescriptionParts,
     duration_string::DurationString,
+    event::Event,
     ports::{
         aoc_client::AocClient,
+        errors::AocClientError,
         get_input::GetInput,
         get_leaderboard::GetLeaderboard,
         get_private_leaderboard::GetPrivateLeaderboard,
@@ -19,9 +33,13 @@ use crate::domain::{
         input_cache::{InputCache, InputCacheError},
     },
     private_leaderboard::PrivateLeaderboard,
+    riddle_example::RiddleExample,
     riddle_part::RiddlePart,
     stars::Stars,
     submission::Submission,
+    submission_outcome::SubmissionOutcome,
+    submission_preview::SubmissionPreview,
+    submission_result::SubmissionResult,
     submission_status::SubmissionStatus,
 };
 
@@ -48,15 +66,19 @@ impl Driver {
                     eprintln!("Cache corrupted. Clearing the cache...");
                     let _ = self.clear_cache().context("Failed to clear the cache")?;
                 }
+                _ if self.configuration.aoc.offline => {
+                    anyhow::bail!(Self::not_cached_error(&format!(
+                        "the input for {}-{:02}",
+                        year, day
+                    )))
+                }
                 _ => {
                     eprintln!("Downloading the input from the server...");
                 }
             },
         };
 
-        let http_client = AocApi::prepare_http_client(&self.configuration);
-        let aoc_api = AocApi::new(http_client, self.configuration.clone());
-        aoc_api.get_input(day, year)
+        self.download_input(&self.aoc_api()?, year, day)
     }
 
     pub fn submit_answer(
@@ -65,11 +87,12 @@ impl Driver {
         day: usize,
         part: RiddlePart,
         answer: String,
-    ) -> Result<()> {
-        let http_client = AocApi::prepare_http_client(&self.configuration);
-        let aoc_api = AocApi::new(http_client, self.configuration.clone());
+        force: bool,
+    ) -> Result<SubmissionOutcome> {
+        let answer = normalise_answer(&answer)?;
+        let aoc_api = self.aoc_api()?;
 
-        let mut cache: Option<SubmissionHistory> = match SubmissionHistory::from_cache(year, day) {
+        let cache: Option<SubmissionHistory> = match SubmissionHistory::from_cache(year, day) {
             Ok(c) => Some(c),
             Err(e) => {
                 eprintln!("Error: {}", e);
@@ -79,74 +102,390 @@ impl Driver {
         };
 
         let submission = Submission::new(part, answer, year, day);
-        if let Some(ref cache) = cache {
-            if let Some(submission_result) = cache.correct_submission(&submission.part) {
-                eprintln!("🎉  You already submitted the correct answer for this part. Here is the result from last time...\n\n");
-                println!("{}", submission_result.message);
-                return Ok(());
-            }
-
-            if let Some(submission_result) = cache.get_result_for_submission(&submission) {
-                eprintln!("♻️  You submitted this answer before and the result was...\n\n");
-                println!("{}", submission_result.message);
-                if let Some(wait_time) = cache.wait_time(&chrono::Utc::now(), &submission.part) {
-                    eprintln!(
-                        "\n🌡️  You still need to wait {} before another submission.",
-                        DurationString::new(wait_time)
-                    );
-                }
-                return Ok(());
-            }
-
-            if let Some(wait_time) = cache.wait_time(&chrono::Utc::now(), &submission.part) {
-                eprintln!("🌡️  You wanted to submit an answer too soon. Please wait {} before submitting again.",
-                DurationString::new(wait_time));
-                return Ok(());
-            }
+        if let Some(outcome) = self.known_outcome(&submission, cache.as_ref(), force) {
+            return Ok(outcome);
         }
         let submission_result = aoc_api
             .submit_answer(submission)
             .context("Submitting the result was unsuccessful")?;
-        eprintln!("Your submission result...\n\n");
-        println!("{}", submission_result.message);
+        Self::record_submission(&submission_result, cache, || {
+            self.download_description(&aoc_api, year, day)
+        })?;
+
+        Ok(SubmissionOutcome::Submitted(submission_result))
+    }
+
+    /// Remembers the result of the sent answer
+    ///
+    /// The cached description of the riddle is outdated once a part is solved,
+    /// so it is downloaded again. The cached one is kept if the download fails.
+    fn record_submission(
+        submission_result: &SubmissionResult,
+        history: Option<SubmissionHistory>,
+        download_description: impl FnOnce() -> Result<HttpDescription>,
+    ) -> Result<()> {
+        let submission = &submission_result.submission;
         if submission_result.status == SubmissionStatus::Correct
             || submission_result.status == SubmissionStatus::Incorrect
             || submission_result.status == SubmissionStatus::TooSoon
         {
-            if let Some(ref mut cache) = cache {
-                cache.add(submission_result);
-                return Ok(cache.save_to_cache()?);
-            } else {
-                let mut cache = SubmissionHistory::new(year, day);
-                cache.add(submission_result);
-                return Ok(cache.save_to_cache()?);
+            let mut history =
+                history.unwrap_or_else(|| SubmissionHistory::new(submission.year, submission.day));
+            history.add(submission_result.clone());
+            history.save_to_cache()?;
+        }
+        if submission_result.status == SubmissionStatus::Correct {
+            if let Err(e) = download_description() {
+                eprintln!(
+                    "Failed to download the description again: {:#}. Run `elv desc --refresh` to update it",
+                    e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Works out what [`Driver::submit_answer`] would do, without sending anything
+    pub fn preview_submission(
+        &self,
+        year: usize,
+        day: usize,
+        part: RiddlePart,
+        answer: String,
+        force: bool,
+    ) -> Result<SubmissionPreview> {
+        let answer = normalise_answer(&answer)?;
+        let history = SubmissionHistory::read_from_cache(year, day)?;
+        let submission = Submission::new(part, answer, year, day);
+        let known_outcome = self.known_outcome(&submission, Some(&history), force);
+        let request = AocApi::describe_answer_request(&submission, &self.configuration)?;
+        Ok(SubmissionPreview {
+            submission,
+            known_outcome,
+            request,
+        })
+    }
+
+    /// The outcome of the submission known without sending the answer
+    ///
+    /// The answer is not sent if the part is already solved, the same answer
+    /// was sent before, the servers asked to wait or the answer looks like
+    /// a mistake and the submission is not forced.
+    fn known_outcome(
+        &self,
+        submission: &Submission,
+        history: Option<&SubmissionHistory>,
+        force: bool,
+    ) -> Option<SubmissionOutcome> {
+        if let Some(history) = history {
+            if let Some(submission_result) = history.correct_submission(&submission.part) {
+                return Some(SubmissionOutcome::AlreadySolved(submission_result.clone()));
+            }
+
+            if let Some(submission_result) = history.get_result_for_submission(submission) {
+                return Some(SubmissionOutcome::Repeated {
+                    result: submission_result.clone(),
+                    wait_time: history.wait_time(&chrono::Utc::now(), &submission.part),
+                });
+            }
+
+            if let Some(wait_time) = history.wait_time(&chrono::Utc::now(), &submission.part) {
+                return Some(SubmissionOutcome::TooSoon(wait_time));
+            }
+        }
+        if !force {
+            let part_one_answer = match submission.part {
+                RiddlePart::One => None,
+                RiddlePart::Two => {
+                    self.known_part_one_answer(history, submission.year, submission.day)
+                }
+            };
+            let warnings = check_answer(&submission.answer, part_one_answer.as_deref());
+            if !warnings.is_empty() {
+                return Some(SubmissionOutcome::Suspicious(warnings));
             }
         }
+        None
+    }
 
+    /// Finds the correct answer to the first part in the history or the cached description
+    fn known_part_one_answer(
+        &self,
+        history: Option<&SubmissionHistory>,
+        year: usize,
+        day: usize,
+    ) -> Option<String> {
+        history
+            .and_then(|history| history.correct_submission(&RiddlePart::One))
+            .map(|result| result.submission.answer.clone())
+            .or_else(|| {
+                let body = FilePageCache::load(&CachedPage::Description { year, day }).ok()?;
+                HttpDescription::new(year as u16, day as u8, body).answer(&RiddlePart::One)
+            })
+    }
+
+    /// Downloads the description of the riddle again and replaces the cached one
+    ///
+    /// The descriptions are cached until a part of the riddle is solved with `elv`.
+    /// Use this if you solved it some other way. The cached description is kept
+    /// if the download fails.
+    pub fn refresh_description(&self, year: usize, day: usize) -> Result<()> {
+        if self.configuration.aoc.offline {
+            anyhow::bail!(
+                "elv is in the offline mode, so the description cannot be downloaded again"
+            );
+        }
+        self.download_description(&self.aoc_api()?, year, day)?;
         Ok(())
     }
 
+    /// Gets the answers submitted so far for the riddle
+    pub fn submission_history(&self, year: usize, day: usize) -> Result<SubmissionHistory> {
+        Ok(SubmissionHistory::from_cache(year, day)?)
+    }
+
     /// Clears the cache of the application
+    ///
+    /// The submission histories are kept. Use [`Driver::remove_cache_entries`]
+    /// to remove them.
     pub fn clear_cache(&self) -> Result<()> {
         FileInputCache::clear()?;
-        SubmissionHistory::clear()?;
+        FilePageCache::clear()?;
         Ok(())
     }
 
-    /// Returns the description of the riddles
-    pub fn get_description(&self, year: usize, day: usize) -> Result<String> {
-        let http_client = AocApi::prepare_http_client(&self.configuration);
-        let aoc_api = AocApi::new(http_client, self.configuration.clone());
-        Ok(aoc_api
-            .get_description::<HttpDescription>(year, day)?
-            .cli_fmt(&self.configuration))
+    /// Lists the entries in the cache
+    pub fn list_cache(&self, filter: &CacheFilter) -> Result<Vec<CacheEntry>> {
+        Ok(CacheInventory::entries(filter)?)
+    }
+
+    /// Returns the contents of the first cache entry matching the filter
+    /// in a human-readable form
+    pub fn show_cache_entry(&self, filter: &CacheFilter) -> Result<String> {
+        let entry = match CacheInventory::entries(filter)?.into_iter().next() {
+            Some(entry) => entry,
+            None => anyhow::bail!("Nothing in the cache matches the selection"),
+        };
+        let contents = std::fs::read(&entry.path)
+            .with_context(|| format!("Failed to read {}", entry.path.display()))?;
+        let day = entry.day.unwrap_or_default();
+        Ok(match entry.kind {
+            CacheKind::Input => String::from_utf8_lossy(&contents).into_owned(),
+            CacheKind::Description => HttpDescription::new(
+                entry.year as u16,
+                day as u8,
+                String::from_utf8_lossy(&contents).into_owned(),
+            )
+            .cli_fmt(&self.configuration),
+            CacheKind::Submissions => {
+                SubmissionHistory::from_cache(entry.year, day)?.cli_fmt(&self.configuration)
+            }
+            CacheKind::Stars => Stars::from_readable(contents.as_slice())?.to_string(),
+            CacheKind::PrivateLeaderboard => {
+                PrivateLeaderboard::from_json(contents.as_slice())?.cli_fmt(&self.configuration)
+            }
+        })
+    }
+
+    /// Removes the entries matching the filter from the cache
+    pub fn remove_cache_entries(&self, filter: &CacheFilter) -> Result<Vec<CacheEntry>> {
+        let entries = CacheInventory::entries(filter)?;
+        CacheInventory::remove(&entries)?;
+        Ok(entries)
+    }
+
+    /// Removes the entries matching the filter and older than `older_than` from the cache
+    pub fn prune_cache(
+        &self,
+        filter: &CacheFilter,
+        older_than: chrono::Duration,
+    ) -> Result<Vec<CacheEntry>> {
+        let now = chrono::Utc::now();
+        let entries = CacheInventory::entries(filter)?
+            .into_iter()
+            .filter(|entry| entry.age(&now) > older_than)
+            .collect::<Vec<_>>();
+        CacheInventory::remove(&entries)?;
+        Ok(entries)
+    }
+
+    /// Exports the cache to a gzipped tarball
+    pub fn export_cache(&self, archive: &std::path::Path) -> Result<()> {
+        Ok(CacheInventory::export(archive)?)
+    }
+
+    /// Imports the cache from a tarball created by [`Driver::export_cache`]
+    pub fn import_cache(&self, archive: &std::path::Path) -> Result<usize> {
+        Ok(CacheInventory::import(archive)?)
+    }
+
+    /// Returns the selected parts of the riddle description formatted for the terminal
+    pub fn get_description(
+        &self,
+        year: usize,
+        day: usize,
+        parts: &DescriptionParts,
+    ) -> Result<String> {
+        let description = self.selected_description(year, day, parts)?;
+        Ok(description.cli_fmt_parts(parts, &self.configuration))
+    }
+
+    /// Returns the original HTML of the selected parts of the riddle description
+    pub fn get_raw_description(
+        &self,
+        year: usize,
+        day: usize,
+        parts: &DescriptionParts,
+    ) -> Result<String> {
+        Ok(self.selected_description(year, day, parts)?.raw_html(parts))
     }
 
     /// Gets the stars for a specified year
     pub fn get_stars(&self, year: i32) -> Result<Stars> {
-        let http_client = AocApi::prepare_http_client(&self.configuration);
-        let aoc_api = AocApi::new(http_client, self.configuration.clone());
-        Ok(aoc_api.get_stars(year)?)
+        if self.configuration.aoc.offline {
+            let stars_page = Self::load_cached_page(&CachedPage::Stars { year })?;
+            return Stars::from_readable(stars_page.as_bytes());
+        }
+        self.aoc_api()?.get_stars(year)
+    }
+
+    /// Downloads the inputs, descriptions and the stars page for a given year,
+    /// so they can be used in the offline mode
+    ///
+    /// Inputs and descriptions that are already cached are not downloaded again.
+    /// A failed download is reported and the rest are still downloaded.
+    pub fn prefetch(&self, year: i32, leaderboard_ids: &[String]) -> Result<()> {
+        let aoc_api = self.aoc_api()?;
+        let (mut downloads, mut failures) = (0, 0);
+        let mut download = |what: String, result: Result<()>| {
+            downloads += 1;
+            if let Err(e) = result {
+                failures += 1;
+                eprintln!("❌ Failed to download {}: {:#}", what, e);
+            }
+        };
+        for day in self.released_days(year as usize, None)? {
+            if FileInputCache::load(year as usize, day).is_err() {
+                eprintln!("Downloading the input for {}-{:02}...", year, day);
+                download(
+                    format!("the input for {}-{:02}", year, day),
+                    self.download_input(&aoc_api, year as usize, day)
+                        .map(|_| ()),
+                );
+            }
+            let page = CachedPage::Description {
+                year: year as usize,
+                day,
+            };
+            if FilePageCache::load(&page).is_err() {
+                eprintln!("Downloading the description for {}-{:02}...", year, day);
+                download(
+                    format!("the description for {}-{:02}", year, day),
+                    self.download_description(&aoc_api, year as usize, day)
+                        .map(|_| ()),
+                );
+            }
+        }
+
+        eprintln!("Downloading the stars page for {}...", year);
+        download(
+            format!("the stars page for {}", year),
+            aoc_api.get_stars(year).map(|_| ()),
+        );
+
+        for leaderboard_id in leaderboard_ids {
+            eprintln!(
+                "Downloading the private leaderboard {} for {}...",
+                leaderboard_id, year
+            );
+            download(
+                format!("the private leaderboard {}", leaderboard_id),
+                aoc_api
+                    .get_private_leaderboard(leaderboard_id, year)
+                    .map(|_| ())
+                    .map_err(anyhow::Error::from),
+            );
+        }
+        if failures > 0 {
+            anyhow::bail!("{} of {} downloads failed", failures, downloads);
+        }
+        Ok(())
+    }
+
+    /// Records the answers shown on the pages of the solved riddles in the submission histories
+    ///
+    /// The riddles solved on another machine or on the website become known
+    /// to `elv`, so it does not submit their answers again. The cached
+    /// descriptions are used if they show the answers, otherwise the riddle
+    /// pages are downloaded again. Returns the number of recorded answers.
+    pub fn sync_history(&self, year: usize) -> Result<usize> {
+        let mut recorded_answers = 0;
+        let event = Event::new(year)?;
+        for day in self.released_days(year, None)? {
+            let parts = match event.has_second_part(day) {
+                true => vec![RiddlePart::One, RiddlePart::Two],
+                false => vec![RiddlePart::One],
+            };
+            recorded_answers += self.sync_day(year, day, parts)?;
+        }
+        Ok(recorded_answers)
+    }
+
+    /// Records the answers to the parts of the riddle missing from its history
+    ///
+    /// The history is written to the cache only if an answer is recorded.
+    fn sync_day(&self, year: usize, day: usize, parts: Vec<RiddlePart>) -> Result<usize> {
+        let mut history = SubmissionHistory::read_from_cache(year, day)?;
+        let missing_parts = parts
+            .into_iter()
+            .filter(|part| history.correct_submission(part).is_none())
+            .collect::<Vec<_>>();
+        if missing_parts.is_empty() {
+            return Ok(0);
+        }
+
+        let cached_description = FilePageCache::load(&CachedPage::Description { year, day })
+            .ok()
+            .map(|body| HttpDescription::new(year as u16, day as u8, body));
+        let description = match cached_description {
+            Some(description)
+                if self.configuration.aoc.offline
+                    || missing_parts
+                        .iter()
+                        .all(|part| description.answer(part).is_some()) =>
+            {
+                description
+            }
+            _ if self.configuration.aoc.offline => return Ok(0),
+            _ => {
+                eprintln!("Downloading the description for {}-{:02}...", year, day);
+                self.download_description(&self.aoc_api()?, year, day)?
+            }
+        };
+
+        let mut recorded_parts = Vec::new();
+        for part in missing_parts {
+            if let Some(answer) = description.answer(&part) {
+                history.add(SubmissionResult::new(
+                    Submission::new(part.clone(), answer, year, day),
+                    SubmissionStatus::Correct,
+                    "Recorded from the riddle page by `elv sync`".to_owned(),
+                    chrono::Utc::now(),
+                    std::time::Duration::ZERO,
+                ));
+                recorded_parts.push(part.to_string());
+            }
+        }
+        if !recorded_parts.is_empty() {
+            history.save_to_cache()?;
+            eprintln!(
+                "{}-{:02}: recorded the answer to part {}",
+                year,
+                day,
+                recorded_parts.join(" and ")
+            );
+        }
+        Ok(recorded_parts.len())
     }
 
     /// Lists the directories used by the application
@@ -164,14 +503,15 @@ impl Driver {
         {
             directories.insert("cache", cache_dir.to_owned());
         }
+        if let Some(project_file) = Configuration::find_project_file() {
+            directories.insert("project", project_file.display().to_string());
+        }
         Ok(directories)
     }
 
     /// Gets the leaderboard for a given year
     pub fn get_leaderboard(&self, year: i32) -> Result<String> {
-        let http_client = AocApi::prepare_http_client(&self.configuration);
-        let aoc_client = AocApi::new(http_client, self.configuration.clone());
-        let leaderboard = aoc_client.get_leaderboard(year)?;
+        let leaderboard = self.aoc_api()?.get_leaderboard(year)?;
 
         Ok(leaderboard.cli_fmt(&self.configuration))
     }
@@ -181,11 +521,47 @@ impl Driver {
         leaderboard_id: &str,
         year: i32,
     ) -> Result<PrivateLeaderboard> {
-        let http_client = AocApi::prepare_http_client(&self.configuration);
-        let aoc_client = AocApi::new(http_client, self.configuration.clone());
+        let page = CachedPage::PrivateLeaderboard {
+            year,
+            id: leaderboard_id.to_owned(),
+        };
+        if self.configuration.aoc.offline {
+            let leaderboard_json = Self::load_cached_page(&page)?;
+            return PrivateLeaderboard::from_json(leaderboard_json.as_bytes())
+                .context("Failed to get the private leaderboard");
+        }
 
-        aoc_client
+        match self
+            .aoc_api()?
             .get_private_leaderboard(leaderboard_id, year)
+        {
+            Err(AocClientError::RuntimeError(e)) if e.is::<ThrottleError>() => {
+                Self::throttled_private_leaderboard(&page, e, &chrono::Utc::now())
+            }
+            result => result.context("Failed to get the private leaderboard"),
+        }
+    }
+
+    /// Serves the cached private leaderboard when it cannot be requested yet
+    ///
+    /// The leaderboards may be requested only every 15 minutes, so checking
+    /// them again in the meantime shows the cached copy.
+    fn throttled_private_leaderboard(
+        page: &CachedPage,
+        throttle_error: anyhow::Error,
+        now: &chrono::DateTime<chrono::Utc>,
+    ) -> Result<PrivateLeaderboard> {
+        let (Ok(leaderboard_json), Some(saved_at)) =
+            (FilePageCache::load(page), FilePageCache::saved_at(page))
+        else {
+            return Err(throttle_error.context("Failed to get the private leaderboard"));
+        };
+        eprintln!(
+            "⚠️ {}. Showing the leaderboard downloaded {} ago",
+            throttle_error,
+            DurationString::new(*now - saved_at)
+        );
+        PrivateLeaderboard::from_json(leaderboard_json.as_bytes())
             .context("Failed to get the private leaderboard")
     }
 
@@ -194,15 +570,204 @@ impl Driver {
     }
 
     pub fn set_config_key(key: &str, value: String) -> Result<()> {
-        Configuration::update_configuration_key(key, value)?;
+        Configuration::set_configuration_key(key, &value)?;
+        Ok(())
+    }
+
+    pub fn get_config_key(key: &str) -> Result<Option<String>> {
+        Ok(Configuration::get_configuration_key(key)?)
+    }
+
+    pub fn unset_config_key(key: &str) -> Result<()> {
+        Configuration::unset_configuration_key(key)?;
         Ok(())
     }
 
+    /// Returns the days from the range with already released riddles
+    ///
+    /// Goes through all the days of the event if no range is given.
+    pub fn released_days(&self, year: usize, days: Option<DayRange>) -> Result<Vec<usize>> {
+        let now = chrono::Utc::now();
+        let days = match days {
+            Some(days) => days,
+            None => Event::new(year)?.day_range(),
+        };
+        let mut released_days = Vec::new();
+        for day in days.days() {
+            if self.is_input_released_yet(year, day, &now)? {
+                released_days.push(day);
+            }
+        }
+        Ok(released_days)
+    }
+
+    /// Returns the moment the riddle unlocks
+    pub fn release_time(
+        &self,
+        year: usize,
+        day: usize,
+    ) -> Result<chrono::DateTime<chrono::FixedOffset>> {
+        Ok(Event::new(year)?.release_time(day)?)
+    }
+
+    /// Returns the input if it is in the cache, without sending any request
+    pub fn cached_input(&self, year: usize, day: usize) -> Option<String> {
+        FileInputCache::load(year, day).ok()
+    }
+
+    /// Returns the examples of the riddle with their answers
+    ///
+    /// The examples come from the description of the riddle and from
+    /// the examples file named after the `solutions.examples` template.
+    pub fn riddle_examples(&self, year: usize, day: usize) -> Result<Vec<RiddleExample>> {
+        let examples_path = self.riddle_path(
+            &PathTemplate::new(&self.configuration.solutions.examples),
+            year,
+            day,
+            None,
+        )?;
+        let examples_file = ExamplesFile::load(&examples_path)?;
+        let mut examples = if examples_file.replace {
+            Vec::new()
+        } else {
+            self.description(year, day)?.examples()
+        };
+        examples.extend(examples_file.examples());
+        Ok(examples)
+    }
+
+    /// Renders the path template for the riddle, looking up its title if the template needs it
+    pub fn riddle_path(
+        &self,
+        template: &PathTemplate,
+        year: usize,
+        day: usize,
+        part: Option<&RiddlePart>,
+    ) -> Result<std::path::PathBuf> {
+        let title = match template.depends_on_title() {
+            true => self
+                .description(year, day)?
+                .title()
+                .context("The riddle page has no title")?,
+            false => String::new(),
+        };
+        Ok(template.render_riddle(year, day, part, &title))
+    }
+
+    /// Returns the correct answers to the parts of the riddle known to `elv`
+    ///
+    /// The answers come from the file set in `solutions.answers`
+    /// and from the history of your submissions.
+    pub fn known_answers(&self, year: usize, day: usize) -> Result<Vec<(RiddlePart, String)>> {
+        let answers_file = AnswersFile::load(&self.configuration.solutions.answers)?;
+        let history = SubmissionHistory::from_cache(year, day)?;
+        Ok([RiddlePart::One, RiddlePart::Two]
+            .into_iter()
+            .filter_map(|part| {
+                let answer = answers_file.answer(year, day, &part).or_else(|| {
+                    history
+                        .correct_submission(&part)
+                        .map(|result| result.submission.answer.clone())
+                })?;
+                Some((part, answer))
+            })
+            .collect())
+    }
+
+    /// Runs the solution set in `solutions.command` on the input and returns its answer
+    pub fn run_solution(
+        &self,
+        year: usize,
+        day: usize,
+        part: &RiddlePart,
+        input: &str,
+    ) -> Result<String> {
+        let command = self.configuration.solutions.command.as_deref().context(
+            "No solution to run. Set it with `elv config set solutions.command <COMMAND>`",
+        )?;
+        Ok(SolutionRunner::new(command).run(year, day, part, input)?)
+    }
+
     pub(crate) fn guess_riddle_part(&self, year: usize, day: usize) -> Result<RiddlePart> {
-        let http_client = AocApi::prepare_http_client(&self.configuration);
-        let aoc_client = AocApi::new(http_client, self.configuration.clone());
+        self.description(year, day)?.unsolved_part()
+    }
+
+    /// Builds the client of the Advent of Code API
+    ///
+    /// Fails in the offline mode, so no request ever leaves the machine.
+    fn aoc_api(&self) -> Result<AocApi> {
+        if self.configuration.aoc.offline {
+            anyhow::bail!(
+                "elv is in the offline mode and cannot connect to the Advent of Code servers"
+            );
+        }
+        let http_client = AocApi::prepare_http_client(&self.configuration)?;
+        Ok(AocApi::new(http_client, self.configuration.clone()))
+    }
+
+    fn selected_description(
+        &self,
+        year: usize,
+        day: usize,
+        parts: &DescriptionParts,
+    ) -> Result<HttpDescription> {
+        let description = self.description(year, day)?;
+        if description.selected_parts(parts).is_empty() {
+            anyhow::bail!(
+                "The second part of the riddle is locked. Solve the first part to unlock it"
+            );
+        }
+        Ok(description)
+    }
+
+    /// Returns the cached description of the riddle or downloads it
+    ///
+    /// The cached page shows the riddle as it was when it was downloaded,
+    /// so it is removed from the cache after solving a part of the riddle.
+    fn description(&self, year: usize, day: usize) -> Result<HttpDescription> {
+        let page = CachedPage::Description { year, day };
+        if self.configuration.aoc.offline {
+            let body = Self::load_cached_page(&page)?;
+            return Ok(HttpDescription::new(year as u16, day as u8, body));
+        }
+        if let Ok(body) = FilePageCache::load(&page) {
+            return Ok(HttpDescription::new(year as u16, day as u8, body));
+        }
+        self.download_description(&self.aoc_api()?, year, day)
+    }
 
-        aoc_client.find_unsolved_part(year, day)
+    fn download_input(&self, aoc_api: &AocApi, year: usize, day: usize) -> Result<String> {
+        let input = aoc_api.get_input(day, year)?;
+        FileInputCache::save(&input, year, day)?;
+        Ok(input)
+    }
+
+    fn download_description(
+        &self,
+        aoc_api: &AocApi,
+        year: usize,
+        day: usize,
+    ) -> Result<HttpDescription> {
+        let description = aoc_api.get_description::<HttpDescription>(year, day)?;
+        FilePageCache::save(&CachedPage::Description { year, day }, description.body())?;
+        Ok(description)
+    }
+
+    fn load_cached_page(page: &CachedPage) -> Result<String> {
+        match FilePageCache::load(page) {
+            Ok(body) => Ok(body),
+            Err(PageCacheError::Empty(_)) => {
+                anyhow::bail!(Self::not_cached_error(&page.to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn not_cached_error(what: &str) -> String {
+        format!(
+            "elv is in the offline mode, but {} is not cached. Run `elv prefetch` while online to cache it",
+            what
+        )
     }
 
     fn is_input_released_yet(
@@ -211,21 +776,14 @@ impl Driver {
         day: usize,
         now: &chrono::DateTime<chrono::Utc>,
     ) -> Result<bool> {
-        let input_release_time = match chrono::FixedOffset::west_opt(60 * 60 * 5)
-            .unwrap()
-            .with_ymd_and_hms(year as i32, 12, day as u32, 0, 0, 0)
-            .single()
-        {
-            None => anyhow::bail!("Invalid date"),
-            Some(time) => time,
-        };
-
-        Ok(now >= &input_release_time)
+        Ok(now >= &self.release_time(year, day)?)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use chrono::TimeZone;
+
     use super::*;
 
     #[test]
@@ -258,6 +816,210 @@ mod tests {
         let input = driver.input(0, 0);
         assert!(input.is_err());
         let error = input.err().unwrap();
-        assert!(error.to_string() == "Invalid date");
+        assert_eq!(
+            error.to_string(),
+            "There is no Advent of Code in 0. The first event was held in 2015"
+        );
+    }
+
+    fn resource(name: &str) -> String {
+        std::fs::read_to_string(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/resources")
+                .join(name),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn reuses_the_cached_description_of_an_unsolved_riddle() {
+        let _lock = crate::infrastructure::page_cache::TEST_CACHE_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let page = CachedPage::Description { year: 1000, day: 2 };
+        FilePageCache::save(&page, &resource("riddle-description-no-part-done.html")).unwrap();
+
+        // Without a token, a download would fail
+        let description = Driver::default().get_raw_description(1000, 2, &DescriptionParts::All);
+        FilePageCache::remove(&page).unwrap();
+        assert!(description.unwrap().contains("day-desc"));
+    }
+
+    #[test]
+    fn refreshes_the_description_after_a_correct_answer() {
+        let _lock = crate::infrastructure::page_cache::TEST_CACHE_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let (year, day) = (1000, 3);
+        let page = CachedPage::Description { year, day };
+        let unsolved = resource("riddle-description-no-part-done.html");
+        FilePageCache::save(&page, &unsolved).unwrap();
+        let result = |status| {
+            SubmissionResult::new(
+                Submission::new(RiddlePart::One, "42".to_owned(), year, day),
+                status,
+                String::new(),
+                chrono::Utc::now(),
+                std::time::Duration::ZERO,
+            )
+        };
+
+        let mut downloads = 0;
+        Driver::record_submission(&result(SubmissionStatus::Incorrect), None, || {
+            downloads += 1;
+            anyhow::bail!("Not reached")
+        })
+        .unwrap();
+        assert_eq!(downloads, 0);
+
+        // A failed download keeps the cached description
+        Driver::record_submission(&result(SubmissionStatus::Correct), None, || {
+            downloads += 1;
+            anyhow::bail!("No connection")
+        })
+        .unwrap();
+        assert_eq!(downloads, 1);
+        assert_eq!(FilePageCache::load(&page).unwrap(), unsolved);
+
+        let solved = resource("riddle-description-first-part-done.html");
+        Driver::record_submission(&result(SubmissionStatus::Correct), None, || {
+            FilePageCache::save(&page, &solved)?;
+            Ok(HttpDescription::new(year as u16, day as u8, solved.clone()))
+        })
+        .unwrap();
+        assert_eq!(FilePageCache::load(&page).unwrap(), solved);
+
+        FilePageCache::remove(&page).unwrap();
+        std::fs::remove_file(SubmissionHistory::cache_path(year, day)).unwrap();
+    }
+
+    #[test]
+    fn previews_the_submission_without_side_effects() {
+        let _lock = crate::infrastructure::page_cache::TEST_CACHE_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let (year, day) = (1000, 4);
+        let history_path = SubmissionHistory::cache_path(year, day);
+        // Nothing is sent, so neither the connection settings nor the servers are needed
+        let mut configuration = Configuration::default();
+        configuration.aoc.offline = true;
+        configuration.http.proxy = Some("http://[::1".to_owned());
+        let driver = Driver::new(configuration);
+
+        let preview = driver
+            .preview_submission(year, day, RiddlePart::One, "42".to_owned(), false)
+            .unwrap();
+        assert_eq!(preview.known_outcome, None);
+        assert!(preview.request.contains("answer=42"));
+        assert!(!history_path.exists());
+
+        let mut history = SubmissionHistory::new(year, day);
+        history.add(SubmissionResult::new(
+            Submission::new(RiddlePart::One, "7".to_owned(), year, day),
+            SubmissionStatus::Correct,
+            "That's the right answer!".to_owned(),
+            chrono::Utc::now(),
+            std::time::Duration::ZERO,
+        ));
+        history.save_to_cache().unwrap();
+        let saved_history = std::fs::read_to_string(&history_path).unwrap();
+
+        let preview = driver
+            .preview_submission(year, day, RiddlePart::One, "42".to_owned(), false)
+            .unwrap();
+        let history_after_preview = std::fs::read_to_string(&history_path).unwrap();
+        std::fs::remove_file(&history_path).unwrap();
+        assert!(matches!(
+            preview.known_outcome,
+            Some(SubmissionOutcome::AlreadySolved(_))
+        ));
+        assert_eq!(history_after_preview, saved_history);
+    }
+
+    #[test]
+    fn records_the_answers_shown_on_the_cached_pages() {
+        let _lock = crate::infrastructure::page_cache::TEST_CACHE_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut configuration = Configuration::default();
+        configuration.aoc.offline = true;
+        let driver = Driver::new(configuration);
+        let both_parts = || vec![RiddlePart::One, RiddlePart::Two];
+
+        let unsolved = CachedPage::Description { year: 1000, day: 5 };
+        FilePageCache::save(&unsolved, &resource("riddle-description-no-part-done.html")).unwrap();
+        let recorded = driver.sync_day(1000, 5, both_parts());
+        FilePageCache::remove(&unsolved).unwrap();
+        assert_eq!(recorded.unwrap(), 0);
+        assert!(!SubmissionHistory::cache_path(1000, 5).exists());
+
+        let solved = CachedPage::Description { year: 1000, day: 6 };
+        FilePageCache::save(
+            &solved,
+            &resource("riddle-description-both-parts-done.html"),
+        )
+        .unwrap();
+        let recorded = driver.sync_day(1000, 6, both_parts());
+        let history = SubmissionHistory::read_from_cache(1000, 6);
+        let recorded_again = driver.sync_day(1000, 6, both_parts());
+        FilePageCache::remove(&solved).unwrap();
+        std::fs::remove_file(SubmissionHistory::cache_path(1000, 6)).unwrap();
+
+        assert_eq!(recorded.unwrap(), 2);
+        assert_eq!(recorded_again.unwrap(), 0);
+        let history = history.unwrap();
+        let answer = |part| {
+            history
+                .correct_submission(&part)
+                .map(|result| result.submission.answer.clone())
+        };
+        assert_eq!(answer(RiddlePart::One).as_deref(), Some("ZBDRNPMVH"));
+        assert_eq!(answer(RiddlePart::Two).as_deref(), Some("WDLPFNNNB"));
+    }
+
+    #[test]
+    fn serves_the_cached_private_leaderboard_when_throttled() {
+        let _lock = crate::infrastructure::page_cache::TEST_CACHE_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let page = CachedPage::PrivateLeaderboard {
+            year: 1000,
+            id: "throttled".to_owned(),
+        };
+        let throttle_error =
+            || anyhow::Error::from(ThrottleError::TooManyRequests("Try again later".to_owned()));
+        let now = chrono::Utc::now();
+
+        let missing = Driver::throttled_private_leaderboard(&page, throttle_error(), &now);
+        assert!(format!("{:#}", missing.unwrap_err()).contains("Try again later"));
+
+        FilePageCache::save(&page, &resource("private-leaderboard.json")).unwrap();
+        let cached = Driver::throttled_private_leaderboard(&page, throttle_error(), &now);
+        FilePageCache::remove(&page).unwrap();
+        assert_eq!(cached.unwrap().entries.len(), 4);
+    }
+
+    #[test]
+    fn offline_driver_does_not_reach_the_servers() {
+        let _lock = crate::infrastructure::page_cache::TEST_CACHE_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut configuration = Configuration::default();
+        configuration.aoc.offline = true;
+        let driver = Driver::new(configuration);
+
+        let description = driver.get_description(1000, 25, &DescriptionParts::All);
+        assert!(description.is_err());
+        assert!(description
+            .unwrap_err()
+            .to_string()
+            .contains("is not cached"));
+
+        let leaderboard = driver.get_leaderboard(1000);
+        assert!(leaderboard.is_err());
+        assert!(leaderboard
+            .unwrap_err()
+            .to_string()
+            .contains("offline mode"));
     }
 }
@@ -0,0 +1,113 @@
+use thiserror::Error;
+
+use crate::domain::{
+    riddle_example::{ExampleSource, RiddleExample},
+    riddle_part::RiddlePart,
+};
+
+#[derive(Error, Debug)]
+pub enum ExamplesFileError {
+    #[error("Failed to read the examples file {0}")]
+    Read(String),
+
+    #[error("The examples file {0} is not valid: {1}")]
+    Parse(String, String),
+}
+
+/// The examples of a riddle written down by the user
+///
+/// The file is written in `TOML`:
+///
+/// ```toml
+/// # Drop the examples found in the description of the riddle
+/// replace = true
+///
+/// [[example]]
+/// part = "one"
+/// input = """
+/// 1abc2
+/// pqr3stu8vwx
+/// """
+/// answer = "50"
+/// ```
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct ExamplesFile {
+    /// If `true`, the examples found in the description are not used
+    #[serde(default)]
+    pub replace: bool,
+
+    #[serde(default, rename = "example")]
+    examples: Vec<ExampleEntry>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ExampleEntry {
+    part: RiddlePart,
+    input: String,
+    answer: String,
+}
+
+impl ExamplesFile {
+    /// Reads the examples file, or returns no examples if the file does not exist
+    pub fn load(path: &std::path::Path) -> Result<Self, ExamplesFileError> {
+        if !path.exists() {
+            return Ok(ExamplesFile::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|_| ExamplesFileError::Read(path.display().to_string()))?;
+        Self::parse(&contents)
+            .map_err(|e| ExamplesFileError::Parse(path.display().to_string(), e.to_string()))
+    }
+
+    fn parse(contents: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(contents)
+    }
+
+    pub fn examples(&self) -> Vec<RiddleExample> {
+        self.examples
+            .iter()
+            .map(|entry| {
+                RiddleExample::new(
+                    entry.part.clone(),
+                    entry.input.clone(),
+                    entry.answer.trim().to_owned(),
+                    ExampleSource::ExamplesFile,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_examples() {
+        let file = ExamplesFile::parse(
+            r#"
+replace = true
+
+[[example]]
+part = "two"
+input = """
+two1nine
+"""
+answer = "29"
+"#,
+        )
+        .unwrap();
+
+        assert!(file.replace);
+        assert_eq!(
+            file.examples(),
+            vec![RiddleExample::new(
+                RiddlePart::Two,
+                "two1nine\n".to_owned(),
+                "29".to_owned(),
+                ExampleSource::ExamplesFile
+            )]
+        );
+        assert!(!ExamplesFile::parse("").unwrap().replace);
+    }
+}
@@ -1,6 +1,14 @@
-use crate::{domain::description::Description, Configuration};
+use crate::{
+    domain::{
+        description::Description,
+        description_parts::DescriptionParts,
+        riddle_example::{ExampleSource, RiddleExample},
+        riddle_part::RiddlePart,
+    },
+    Configuration,
+};
 
-use super::cli_display::CliDisplay;
+use super::{cli_display::CliDisplay, rich_text};
 
 pub struct HttpDescription {
     year: u16,
@@ -9,6 +17,15 @@ pub struct HttpDescription {
 }
 
 impl HttpDescription {
+    pub fn new(year: u16, day: u8, body: String) -> Self {
+        HttpDescription { year, day, body }
+    }
+
+    /// Returns the HTML of the whole riddle page
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
     pub fn part_one(&self) -> Option<String> {
         let part_one_selector = scraper::Selector::parse(".day-desc").unwrap();
         let binding = scraper::Html::parse_document(&self.body);
@@ -42,6 +59,175 @@ impl HttpDescription {
             .skip(1)
             .next()
     }
+
+    /// Returns the correct answer to the part of the riddle, if it is solved
+    pub fn answer(&self, part: &RiddlePart) -> Option<String> {
+        let answer_paragraph = match part {
+            RiddlePart::One => self.part_one_answer(),
+            RiddlePart::Two => self.part_two_answer(),
+        }?;
+        let code_selector = scraper::Selector::parse("code").unwrap();
+        scraper::Html::parse_fragment(&answer_paragraph)
+            .select(&code_selector)
+            .next()
+            .map(|code| code.text().collect())
+    }
+
+    /// Returns the title of the riddle, e.g. `Calorie Counting`
+    pub fn title(&self) -> Option<String> {
+        let header_selector = scraper::Selector::parse("article.day-desc > h2").unwrap();
+        let header: String = scraper::Html::parse_document(&self.body)
+            .select(&header_selector)
+            .next()?
+            .text()
+            .collect();
+        let header = header.trim_matches(|c: char| c == '-' || c.is_whitespace());
+        let title = header.split_once(": ").map_or(header, |(_, title)| title);
+        Some(title.to_owned())
+    }
+
+    /// Returns the `<article>` elements of the riddle parts as they are on the riddle page
+    pub fn articles(&self) -> Vec<String> {
+        let article_selector = scraper::Selector::parse("article.day-desc").unwrap();
+        scraper::Html::parse_document(&self.body)
+            .select(&article_selector)
+            .map(|e| e.html())
+            .collect()
+    }
+
+    /// Returns the riddle parts to show out of the ones unlocked so far
+    pub fn selected_parts(&self, parts: &DescriptionParts) -> Vec<RiddlePart> {
+        let is_part_two_unlocked = self.part_two().is_some();
+        match parts {
+            DescriptionParts::All if is_part_two_unlocked => vec![RiddlePart::One, RiddlePart::Two],
+            DescriptionParts::All => vec![RiddlePart::One],
+            DescriptionParts::Only(RiddlePart::Two) if !is_part_two_unlocked => vec![],
+            DescriptionParts::Only(part) => vec![part.clone()],
+            DescriptionParts::New if is_part_two_unlocked => vec![RiddlePart::Two],
+            DescriptionParts::New => vec![RiddlePart::One],
+        }
+    }
+
+    /// Returns the HTML of the selected riddle parts followed by their answers
+    pub fn parts_html(&self, parts: &DescriptionParts) -> String {
+        self.selected_parts(parts)
+            .iter()
+            .flat_map(|part| match part {
+                RiddlePart::One => [self.part_one(), self.part_one_answer()],
+                RiddlePart::Two => [self.part_two(), self.part_two_answer()],
+            })
+            .flatten()
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns the `<article>` elements of the selected riddle parts
+    pub fn raw_html(&self, parts: &DescriptionParts) -> String {
+        let articles = self.articles();
+        self.selected_parts(parts)
+            .iter()
+            .filter_map(|part| articles.get(part.clone() as usize - 1))
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Formats the selected riddle parts for the terminal
+    pub fn cli_fmt_parts(&self, parts: &DescriptionParts, configuration: &Configuration) -> String {
+        let description = self.parts_html(parts);
+        if configuration.cli.rich_descriptions {
+            return rich_text::render(
+                &description,
+                configuration.cli.output_width as usize,
+                configuration.cli.easter_eggs,
+                colored::control::SHOULD_COLORIZE.should_colorize(),
+            );
+        }
+        html2text::from_read_with_decorator(
+            description.as_bytes(),
+            configuration.cli.output_width as usize,
+            html2text::render::text_renderer::TrivialDecorator::new(),
+        )
+    }
+
+    /// Finds the example inputs of the riddle parts and their answers in the text
+    ///
+    /// The example input is the code block introduced with "For example:"
+    /// or, failing that, the first code block of the first part. The second
+    /// part reuses the input of the first one unless it introduces its own.
+    /// The answer is the last emphasised piece of code of the part, which is
+    /// how the riddles present the result for the example.
+    pub fn examples(&self) -> Vec<RiddleExample> {
+        let article_selector = scraper::Selector::parse("article.day-desc").unwrap();
+        let document = scraper::Html::parse_document(&self.body);
+        let mut examples = Vec::new();
+        let mut previous_input: Option<String> = None;
+        for (article, part) in document
+            .select(&article_selector)
+            .zip([RiddlePart::One, RiddlePart::Two])
+        {
+            let input = Self::introduced_example(&article).or_else(|| match part {
+                RiddlePart::One => Self::first_code_block(&article),
+                RiddlePart::Two => previous_input.clone(),
+            });
+            if let (Some(input), Some(answer)) = (&input, Self::emphasised_answer(&article)) {
+                examples.push(RiddleExample::new(
+                    part,
+                    input.clone(),
+                    answer,
+                    ExampleSource::Description,
+                ));
+            }
+            previous_input = input;
+        }
+        examples
+    }
+
+    fn introduced_example(article: &scraper::ElementRef) -> Option<String> {
+        let pre_selector = scraper::Selector::parse("pre").unwrap();
+        article
+            .select(&pre_selector)
+            .find(|pre| {
+                pre.prev_siblings()
+                    .find_map(scraper::ElementRef::wrap)
+                    .is_some_and(|sibling| {
+                        sibling.value().name() == "p"
+                            && sibling
+                                .text()
+                                .collect::<String>()
+                                .trim_end()
+                                .to_lowercase()
+                                .ends_with("example:")
+                    })
+            })
+            .map(|pre| pre.text().collect())
+    }
+
+    fn first_code_block(article: &scraper::ElementRef) -> Option<String> {
+        let pre_selector = scraper::Selector::parse("pre").unwrap();
+        article
+            .select(&pre_selector)
+            .next()
+            .map(|pre| pre.text().collect())
+    }
+
+    fn emphasised_answer(article: &scraper::ElementRef) -> Option<String> {
+        let answer_selector = scraper::Selector::parse("code > em, em > code").unwrap();
+        article
+            .select(&answer_selector)
+            .last()
+            .map(|answer| answer.text().collect::<String>().trim().to_owned())
+            .filter(|answer| !answer.is_empty())
+    }
+
+    /// Returns the first part of the riddle without an answer
+    pub fn unsolved_part(&self) -> Result<RiddlePart, anyhow::Error> {
+        match (self.part_one_answer(), self.part_two_answer()) {
+            (None, _) => Ok(RiddlePart::One),
+            (Some(_), None) => Ok(RiddlePart::Two),
+            (_, _) => anyhow::bail!("Both parts answered"),
+        }
+    }
 }
 
 impl TryFrom<reqwest::blocking::Response> for HttpDescription {
@@ -88,17 +274,7 @@ impl Description for HttpDescription {
 
 impl std::fmt::Display for HttpDescription {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let description = [
-            self.part_one(),
-            self.part_one_answer(),
-            self.part_two(),
-            self.part_two_answer(),
-        ]
-        .iter()
-        .filter(|part| part.is_some())
-        .map(|part| part.as_deref().unwrap())
-        .collect::<Vec<_>>()
-        .join("\n");
+        let description = self.parts_html(&DescriptionParts::All);
 
         f.write_str(&html2text::from_read_with_decorator(
             description.as_bytes(),
@@ -110,22 +286,7 @@ impl std::fmt::Display for HttpDescription {
 
 impl CliDisplay for HttpDescription {
     fn cli_fmt(&self, configuration: &Configuration) -> String {
-        let description = [
-            self.part_one(),
-            self.part_one_answer(),
-            self.part_two(),
-            self.part_two_answer(),
-        ]
-        .iter()
-        .filter(|part| part.is_some())
-        .map(|part| part.as_deref().unwrap())
-        .collect::<Vec<_>>()
-        .join("\n");
-        html2text::from_read_with_decorator(
-            description.as_bytes(),
-            configuration.cli.output_width as usize,
-            html2text::render::text_renderer::TrivialDecorator::new(),
-        )
+        self.cli_fmt_parts(&DescriptionParts::All, configuration)
     }
 }
 
@@ -168,6 +329,8 @@ mod tests {
         assert!(description.part_one_answer().is_some());
         assert!(description.part_two().is_some());
         assert!(description.part_two_answer().is_none());
+        assert!(description.answer(&RiddlePart::One).is_some());
+        assert!(description.answer(&RiddlePart::Two).is_none());
     }
 
     #[test]
@@ -182,5 +345,58 @@ mod tests {
         assert!(description.part_one_answer().is_some());
         assert!(description.part_two().is_some());
         assert!(description.part_two_answer().is_some());
+        assert_eq!(description.title(), Some("Supply Stacks".to_owned()));
+    }
+
+    #[test]
+    fn finds_the_examples_with_their_answers() {
+        let description = HttpDescription {
+            year: 2022,
+            day: 5,
+            body: get_resource_file("riddle-description-both-parts-done.html"),
+        };
+
+        let examples = description.examples();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].part, RiddlePart::One);
+        assert_eq!(examples[0].answer, "CMZ");
+        assert!(examples[0].input.starts_with("    [D]\n[N] [C]"));
+        assert!(examples[0].input.ends_with("move 1 from 1 to 2\n"));
+        assert_eq!(examples[1].part, RiddlePart::Two);
+        assert_eq!(examples[1].answer, "MCD");
+        assert_eq!(examples[1].input, examples[0].input);
+    }
+
+    #[test]
+    fn selects_the_parts_to_show() {
+        let first_part_done = HttpDescription {
+            year: 2022,
+            day: 1,
+            body: get_resource_file("riddle-description-first-part-done.html"),
+        };
+        let no_part_done = HttpDescription {
+            year: 2022,
+            day: 1,
+            body: get_resource_file("riddle-description-no-part-done.html"),
+        };
+
+        assert_eq!(
+            first_part_done.selected_parts(&DescriptionParts::New),
+            vec![RiddlePart::Two]
+        );
+        assert_eq!(
+            no_part_done.selected_parts(&DescriptionParts::New),
+            vec![RiddlePart::One]
+        );
+        assert!(no_part_done
+            .selected_parts(&DescriptionParts::Only(RiddlePart::Two))
+            .is_empty());
+
+        let part_two = first_part_done.parts_html(&DescriptionParts::Only(RiddlePart::Two));
+        assert!(part_two.contains("--- Part Two ---"));
+        assert!(!part_two.contains("--- Day 22"));
+        let raw = first_part_done.raw_html(&DescriptionParts::Only(RiddlePart::Two));
+        assert!(raw.starts_with("<article class=\"day-desc\">"));
+        assert!(raw.contains("--- Part Two ---"));
     }
 }
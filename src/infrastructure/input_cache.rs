@@ -64,6 +64,9 @@ mod tests {
 
     #[test]
     fn cache_tests() -> Result<(), InputCacheError> {
+        let _lock = crate::infrastructure::page_cache::TEST_CACHE_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
         let input = "test input";
         let year = 1000;
         let day = 1;
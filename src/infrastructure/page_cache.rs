@@ -0,0 +1,179 @@
+use thiserror::Error;
+
+use super::configuration::Configuration;
+
+#[derive(Error, Debug)]
+pub enum PageCacheError {
+    #[error("Failed to save the page to the cache: {0}")]
+    Save(String),
+
+    #[error("Failed to load the page from the cache: {0}")]
+    Load(String),
+
+    #[error("Failed to clear the page cache: {0}")]
+    Clear(String),
+
+    #[error("{0}")]
+    Empty(String),
+}
+
+/// Serialises the tests using the cache directory, so they do not see each other's files
+#[cfg(test)]
+pub(crate) static TEST_CACHE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// A page downloaded from the Advent of Code servers that can be served from the cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CachedPage {
+    Description { year: usize, day: usize },
+    Stars { year: i32 },
+    PrivateLeaderboard { year: i32, id: String },
+}
+
+impl CachedPage {
+    fn cache_path(&self) -> std::path::PathBuf {
+        let cache_dir = Configuration::get_project_directories()
+            .cache_dir()
+            .to_path_buf();
+        match self {
+            CachedPage::Description { year, day } => cache_dir
+                .join("descriptions")
+                .join(format!("description-{}-{:02}.html", year, day)),
+            CachedPage::Stars { year } => {
+                cache_dir.join("stars").join(format!("stars-{}.html", year))
+            }
+            CachedPage::PrivateLeaderboard { year, id } => {
+                cache_dir.join("private-leaderboards").join(format!(
+                    "private-leaderboard-{}-{}.json",
+                    year,
+                    id.replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+                ))
+            }
+        }
+    }
+
+    fn cache_directories() -> Vec<std::path::PathBuf> {
+        let binding = Configuration::get_project_directories();
+        let cache_dir = binding.cache_dir();
+        ["descriptions", "stars", "private-leaderboards"]
+            .iter()
+            .map(|dir| cache_dir.join(dir))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for CachedPage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CachedPage::Description { year, day } => {
+                write!(f, "the description for {}-{:02}", year, day)
+            }
+            CachedPage::Stars { year } => write!(f, "the stars page for {}", year),
+            CachedPage::PrivateLeaderboard { year, id } => {
+                write!(f, "the private leaderboard {} for {}", id, year)
+            }
+        }
+    }
+}
+
+pub struct FilePageCache;
+
+impl FilePageCache {
+    pub fn save(page: &CachedPage, body: &str) -> Result<(), PageCacheError> {
+        let cache_path = page.cache_path();
+        let cache_dir = cache_path.parent().unwrap();
+        std::fs::create_dir_all(cache_dir).map_err(|_| {
+            PageCacheError::Save(format!(
+                "Failed to create cache directory: {}",
+                cache_dir.display()
+            ))
+        })?;
+        std::fs::write(&cache_path, body).map_err(|_| {
+            PageCacheError::Save(format!(
+                "Failed to write cache file: {}",
+                cache_path.display()
+            ))
+        })
+    }
+
+    pub fn load(page: &CachedPage) -> Result<String, PageCacheError> {
+        let cache_path = page.cache_path();
+        if !cache_path.exists() {
+            return Err(PageCacheError::Empty(format!("No cached copy of {}", page)));
+        }
+        std::fs::read_to_string(&cache_path).map_err(|_| {
+            PageCacheError::Load(format!(
+                "Failed to read cache file: {}",
+                cache_path.display()
+            ))
+        })
+    }
+
+    /// Returns when the cached copy of the page was saved, if there is one
+    pub fn saved_at(page: &CachedPage) -> Option<chrono::DateTime<chrono::Utc>> {
+        let modified = std::fs::metadata(page.cache_path()).ok()?.modified().ok()?;
+        Some(modified.into())
+    }
+
+    /// Removes the cached copy of the page, so it is downloaded again the next time
+    #[cfg(test)]
+    pub fn remove(page: &CachedPage) -> Result<(), PageCacheError> {
+        let cache_path = page.cache_path();
+        if !cache_path.exists() {
+            return Ok(());
+        }
+        std::fs::remove_file(&cache_path).map_err(|_| {
+            PageCacheError::Clear(format!(
+                "Failed to remove cache file: {}",
+                cache_path.display()
+            ))
+        })
+    }
+
+    pub fn clear() -> Result<(), PageCacheError> {
+        for cache_dir in CachedPage::cache_directories() {
+            if cache_dir.exists() {
+                std::fs::remove_dir_all(&cache_dir).map_err(|_| {
+                    PageCacheError::Clear(format!(
+                        "Failed to remove cache directory: {}",
+                        cache_dir.display()
+                    ))
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_tests() -> Result<(), PageCacheError> {
+        let _lock = TEST_CACHE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let page = CachedPage::Description { year: 1000, day: 1 };
+        let body = "<main><article class=\"day-desc\">test</article></main>";
+        FilePageCache::save(&page, body)?;
+        assert_eq!(FilePageCache::load(&page)?, body);
+
+        FilePageCache::remove(&page)?;
+        assert!(matches!(
+            FilePageCache::load(&page),
+            Err(PageCacheError::Empty(_))
+        ));
+        FilePageCache::remove(&page)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn private_leaderboard_ids_cannot_escape_the_cache_directory() {
+        let page = CachedPage::PrivateLeaderboard {
+            year: 2022,
+            id: "../../123".to_owned(),
+        };
+        assert!(page
+            .cache_path()
+            .ends_with("private-leaderboards/private-leaderboard-2022-______123.json"));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::domain::riddle_part::RiddlePart;
+
+/// A path with placeholders filled in for every riddle
+///
+/// Supported placeholders:
+/// * `{year}` - the year of the riddle, e.g. `2022`
+/// * `{day}` - the day of the riddle, e.g. `7`
+/// * `{day:02}` - the day of the riddle padded with zeros, e.g. `07`
+/// * `{part}` - the part of the riddle, e.g. `2`, or `all` if the file covers both parts
+/// * `{title}` - the title of the riddle in lowercase words joined with dashes,
+///   e.g. `calorie-counting`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathTemplate {
+    template: String,
+}
+
+impl PathTemplate {
+    pub fn new(template: &str) -> Self {
+        PathTemplate {
+            template: template.to_owned(),
+        }
+    }
+
+    /// Returns true if the template produces a different path for every day
+    pub fn depends_on_day(&self) -> bool {
+        self.template.contains("{day}") || self.template.contains("{day:02}")
+    }
+
+    /// Returns true if the riddle title is needed to render the template
+    pub fn depends_on_title(&self) -> bool {
+        self.template.contains("{title}")
+    }
+
+    pub fn render(&self, year: usize, day: usize) -> std::path::PathBuf {
+        self.render_riddle(year, day, None, "")
+    }
+
+    /// Renders the template for the part of the riddle with the given title
+    pub fn render_riddle(
+        &self,
+        year: usize,
+        day: usize,
+        part: Option<&RiddlePart>,
+        title: &str,
+    ) -> std::path::PathBuf {
+        let part = match part {
+            Some(part) => (part.clone() as u8).to_string(),
+            None => "all".to_owned(),
+        };
+        std::path::PathBuf::from(
+            self.template
+                .replace("{year}", &year.to_string())
+                .replace("{day:02}", &format!("{:02}", day))
+                .replace("{day}", &day.to_string())
+                .replace("{part}", &part)
+                .replace("{title}", &slug(title)),
+        )
+    }
+}
+
+/// Turns the title into lowercase words joined with dashes, safe to use in a path
+fn slug(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_the_placeholders() {
+        let template = PathTemplate::new("inputs/{year}/day{day:02}.txt");
+        assert!(template.depends_on_day());
+        assert_eq!(
+            template.render(2022, 3),
+            std::path::PathBuf::from("inputs/2022/day03.txt")
+        );
+        assert_eq!(
+            PathTemplate::new("{year}-{day}").render(2015, 25),
+            std::path::PathBuf::from("2015-25")
+        );
+        assert!(!PathTemplate::new("input").depends_on_day());
+    }
+
+    #[test]
+    fn renders_the_part_and_the_title() {
+        let template = PathTemplate::new("{year}/{day:02}-{title}/part{part}.md");
+        assert!(template.depends_on_title());
+        assert_eq!(
+            template.render_riddle(2022, 1, Some(&RiddlePart::Two), "Calorie Counting"),
+            std::path::PathBuf::from("2022/01-calorie-counting/part2.md")
+        );
+        assert_eq!(
+            template.render_riddle(2016, 11, None, "Radioisotope Thermoelectric Generators"),
+            std::path::PathBuf::from("2016/11-radioisotope-thermoelectric-generators/partall.md")
+        );
+        assert_eq!(
+            PathTemplate::new("{title}").render_riddle(2020, 7, None, "Handy Haversacks/../.."),
+            std::path::PathBuf::from("handy-haversacks")
+        );
+    }
+}
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 
 #[derive(Debug, Clone, Deserialize)]
 pub(crate) struct PrivateLeaderboardResponse {
-    // event: String,
+    pub event: String,
     // owner_id: u32,
     pub members: HashMap<String, MemberResults>,
 }
@@ -1,41 +1,50 @@
+use anyhow::Context;
+
 use crate::domain::{
+    event::Event,
     private_leaderboard::{PrivateLeaderboard, PrivateLeaderboardEntry},
     solved_parts::SolvedParts,
 };
 
 use super::private_leaderboard_response::{MemberResults, PrivateLeaderboardResponse};
 
-impl From<PrivateLeaderboardResponse> for PrivateLeaderboard {
-    fn from(value: PrivateLeaderboardResponse) -> Self {
+impl TryFrom<PrivateLeaderboardResponse> for PrivateLeaderboard {
+    type Error = anyhow::Error;
+
+    fn try_from(value: PrivateLeaderboardResponse) -> Result<Self, Self::Error> {
+        let year = value
+            .event
+            .parse::<usize>()
+            .with_context(|| format!("`{}` is not the year of an event", value.event))?;
+        let event = Event::new(year)?;
         let entries: Vec<PrivateLeaderboardEntry> = value
             .members
             .values()
-            .map(|member_results| member_results.into())
+            .map(|member_results| entry(member_results, &event))
             .collect();
-        PrivateLeaderboard::new(entries)
+        Ok(PrivateLeaderboard::new(event, entries))
     }
 }
 
-impl From<&MemberResults> for PrivateLeaderboardEntry {
-    fn from(value: &MemberResults) -> Self {
-        let user = value.name.to_owned();
-        let points = value.local_score;
-        let mut stars = vec![SolvedParts::None; 25];
-        value
-            .completion_day_level
-            .iter()
-            .for_each(|(&day, completion)| {
-                stars[day - 1] = match (&completion.stage_one, &completion.stage_two) {
-                    (Some(_), None) => SolvedParts::One,
-                    (Some(_), Some(_)) => SolvedParts::Both,
-                    (_, _) => SolvedParts::None,
-                };
-            });
+fn entry(value: &MemberResults, event: &Event) -> PrivateLeaderboardEntry {
+    let user = value.name.to_owned();
+    let points = value.local_score;
+    let mut stars = vec![SolvedParts::None; event.days()];
+    value
+        .completion_day_level
+        .iter()
+        .filter(|(&day, _)| event.validate_day(day).is_ok())
+        .for_each(|(&day, completion)| {
+            stars[day - 1] = match (&completion.stage_one, &completion.stage_two) {
+                (Some(_), None) => SolvedParts::One,
+                (Some(_), Some(_)) => SolvedParts::Both,
+                (_, _) => SolvedParts::None,
+            };
+        });
 
-        PrivateLeaderboardEntry {
-            user,
-            points,
-            stars,
-        }
+    PrivateLeaderboardEntry {
+        user,
+        points,
+        stars,
     }
 }
@@ -0,0 +1,103 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::configuration::RetryConfiguration;
+
+/// Decides how many times and how long after a failure a request is repeated
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    configuration: RetryConfiguration,
+}
+
+impl RetryPolicy {
+    pub fn new(configuration: RetryConfiguration) -> Self {
+        Self { configuration }
+    }
+
+    /// The number of times a request is sent before giving up, including the first one
+    pub fn attempts(&self) -> u32 {
+        self.configuration.attempts.max(1)
+    }
+
+    /// The delay before the retry following the given attempt
+    ///
+    /// The delay doubles with each attempt. With the jitter on, the delay
+    /// is picked at random from between its half and its full length,
+    /// so many clients do not retry at the same moment.
+    pub fn delay(&self, attempt: u32) -> std::time::Duration {
+        let exponential = self
+            .configuration
+            .base_delay_ms
+            .saturating_mul(1 << attempt.saturating_sub(1).min(32));
+        let delay = exponential.min(self.configuration.max_delay_ms);
+        let delay = if self.configuration.jitter {
+            delay / 2 + random_up_to(delay - delay / 2)
+        } else {
+            delay
+        };
+        std::time::Duration::from_millis(delay)
+    }
+
+    /// Tells whether the error is worth repeating the request
+    pub fn is_transient(error: &anyhow::Error) -> bool {
+        error
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|error| error.is_timeout() || error.is_connect() || error.is_request())
+    }
+}
+
+fn random_up_to(max: u64) -> u64 {
+    if max == 0 {
+        return 0;
+    }
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    hasher.finish() % (max + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_delay_grows_exponentially_up_to_the_limit() {
+        let policy = RetryPolicy::new(RetryConfiguration {
+            attempts: 5,
+            base_delay_ms: 100,
+            max_delay_ms: 350,
+            jitter: false,
+        });
+        assert_eq!(policy.delay(1), std::time::Duration::from_millis(100));
+        assert_eq!(policy.delay(2), std::time::Duration::from_millis(200));
+        assert_eq!(policy.delay(3), std::time::Duration::from_millis(350));
+        assert_eq!(policy.delay(100), std::time::Duration::from_millis(350));
+    }
+
+    #[test]
+    fn the_jitter_keeps_the_delay_between_its_half_and_full_length() {
+        let policy = RetryPolicy::new(RetryConfiguration {
+            attempts: 3,
+            base_delay_ms: 1000,
+            max_delay_ms: 10000,
+            jitter: true,
+        });
+        for _ in 0..100 {
+            let delay = policy.delay(2);
+            assert!(delay >= std::time::Duration::from_millis(1000));
+            assert!(delay <= std::time::Duration::from_millis(2000));
+        }
+    }
+
+    #[test]
+    fn at_least_one_attempt_is_made() {
+        let policy = RetryPolicy::new(RetryConfiguration {
+            attempts: 0,
+            ..Default::default()
+        });
+        assert_eq!(policy.attempts(), 1);
+    }
+}
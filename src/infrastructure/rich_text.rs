@@ -0,0 +1,280 @@
+use colored::Colorize;
+use html2text::render::text_renderer::{RichAnnotation, TaggedLine, TaggedString, TextDecorator};
+
+use super::aoc_api::AOC_URL;
+
+/// Renders the HTML of a riddle for a terminal, keeping the looks of the riddle page
+///
+/// The emphasised text and the code are coloured, the preformatted blocks
+/// are put in boxes and the links are listed as footnotes. The hidden
+/// `title` texts are shown next to the text they describe if `reveal_easter_eggs`
+/// is set. The text is left plain unless `colour` is set.
+pub fn render(html: &str, width: usize, reveal_easter_eggs: bool, colour: bool) -> String {
+    let html = if reveal_easter_eggs {
+        reveal_titles(html)
+    } else {
+        html.to_owned()
+    };
+    let lines = html2text::parse(html.as_bytes())
+        .render(width, RiddleDecorator)
+        .into_lines();
+
+    let segments = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(line_number, line)| {
+            line.tagged_strings()
+                .map(move |segment| (line_number, segment))
+        })
+        .collect::<Vec<_>>();
+    let mut links: Vec<String> = Vec::new();
+    let mut rendered_lines = vec![String::new(); lines.len()];
+    let mut is_preformatted = vec![false; lines.len()];
+    for (index, (line_number, segment)) in segments.iter().enumerate() {
+        rendered_lines[*line_number].push_str(&style(segment, colour));
+        if segment
+            .tag
+            .iter()
+            .any(|annotation| matches!(annotation, RichAnnotation::Preformat(_)))
+        {
+            is_preformatted[*line_number] = true;
+        }
+
+        let link = link_of(segment);
+        let next_link = segments.get(index + 1).and_then(|(_, next)| link_of(next));
+        if let Some(link) = link.filter(|link| Some(*link) != next_link) {
+            let url = absolute_url(link);
+            let footnote = match links.iter().position(|known| *known == url) {
+                Some(position) => position + 1,
+                None => {
+                    links.push(url);
+                    links.len()
+                }
+            };
+            let footnote = format!("[{}]", footnote);
+            rendered_lines[*line_number].push_str(&if colour {
+                footnote.dimmed().to_string()
+            } else {
+                footnote
+            });
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut line_number = 0;
+    while line_number < rendered_lines.len() {
+        if !is_preformatted[line_number] {
+            out.push(rendered_lines[line_number].clone());
+            line_number += 1;
+            continue;
+        }
+        let block_end = (line_number..rendered_lines.len())
+            .find(|&number| !is_preformatted[number])
+            .unwrap_or(rendered_lines.len());
+        out.extend(boxed(
+            &lines_text(&lines[line_number..block_end]),
+            &rendered_lines[line_number..block_end],
+        ));
+        line_number = block_end;
+    }
+
+    if !links.is_empty() {
+        out.push(String::new());
+        out.extend(
+            links
+                .iter()
+                .enumerate()
+                .map(|(index, link)| format!("[{}] {}", index + 1, link)),
+        );
+    }
+    out.join("\n")
+}
+
+/// Annotates the text like `html2text::render::text_renderer::RichDecorator`,
+/// but without marking the code and the strong text with backticks and asterisks
+#[derive(Clone, Copy)]
+struct RiddleDecorator;
+
+impl TextDecorator for RiddleDecorator {
+    type Annotation = RichAnnotation;
+
+    fn decorate_link_start(&mut self, url: &str) -> (String, Self::Annotation) {
+        (String::new(), RichAnnotation::Link(url.to_owned()))
+    }
+
+    fn decorate_link_end(&mut self) -> String {
+        String::new()
+    }
+
+    fn decorate_em_start(&mut self) -> (String, Self::Annotation) {
+        (String::new(), RichAnnotation::Emphasis)
+    }
+
+    fn decorate_em_end(&mut self) -> String {
+        String::new()
+    }
+
+    fn decorate_strong_start(&mut self) -> (String, Self::Annotation) {
+        (String::new(), RichAnnotation::Strong)
+    }
+
+    fn decorate_strong_end(&mut self) -> String {
+        String::new()
+    }
+
+    fn decorate_strikeout_start(&mut self) -> (String, Self::Annotation) {
+        (String::new(), RichAnnotation::Strikeout)
+    }
+
+    fn decorate_strikeout_end(&mut self) -> String {
+        String::new()
+    }
+
+    fn decorate_code_start(&mut self) -> (String, Self::Annotation) {
+        (String::new(), RichAnnotation::Code)
+    }
+
+    fn decorate_code_end(&mut self) -> String {
+        String::new()
+    }
+
+    fn decorate_preformat_first(&mut self) -> Self::Annotation {
+        RichAnnotation::Preformat(false)
+    }
+
+    fn decorate_preformat_cont(&mut self) -> Self::Annotation {
+        RichAnnotation::Preformat(true)
+    }
+
+    fn decorate_image(&mut self, title: &str) -> (String, Self::Annotation) {
+        (title.to_owned(), RichAnnotation::Image)
+    }
+
+    fn header_prefix(&mut self, _level: usize) -> String {
+        String::new()
+    }
+
+    fn quote_prefix(&mut self) -> String {
+        "> ".to_owned()
+    }
+
+    fn unordered_item_prefix(&mut self) -> String {
+        "• ".to_owned()
+    }
+
+    fn ordered_item_prefix(&mut self, i: i64) -> String {
+        format!("{}. ", i)
+    }
+
+    fn make_subblock_decorator(&self) -> Self {
+        RiddleDecorator
+    }
+
+    fn finalise(self) -> Vec<TaggedLine<Self::Annotation>> {
+        Vec::new()
+    }
+}
+
+/// Appends the `title` of each `<span>` to its text
+fn reveal_titles(html: &str) -> String {
+    let title_regex = regex::Regex::new(r#"(?s)<span title="([^"]*)">(.*?)</span>"#)
+        .expect("Parsing a static regex should not fail");
+    title_regex
+        .replace_all(html, "$2<strong> [$1]</strong>")
+        .into_owned()
+}
+
+fn style(segment: &TaggedString<Vec<RichAnnotation>>, colour: bool) -> String {
+    if !colour {
+        return segment.s.clone();
+    }
+    let mut styled = segment.s.normal();
+    for annotation in &segment.tag {
+        styled = match annotation {
+            RichAnnotation::Emphasis => styled.yellow().bold(),
+            RichAnnotation::Strong => styled.magenta().italic(),
+            RichAnnotation::Code => styled.cyan(),
+            RichAnnotation::Link(_) => styled.underline(),
+            RichAnnotation::Strikeout => styled.strikethrough(),
+            _ => styled,
+        };
+    }
+    styled.to_string()
+}
+
+fn link_of(segment: &TaggedString<Vec<RichAnnotation>>) -> Option<&String> {
+    segment.tag.iter().find_map(|annotation| match annotation {
+        RichAnnotation::Link(link) => Some(link),
+        _ => None,
+    })
+}
+
+fn absolute_url(link: &str) -> String {
+    if link.starts_with('/') {
+        format!("{}{}", AOC_URL, link)
+    } else {
+        link.to_owned()
+    }
+}
+
+/// The text of the lines without the colours, used to measure them
+fn lines_text(lines: &[TaggedLine<Vec<RichAnnotation>>]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            line.tagged_strings()
+                .map(|segment| segment.s.as_str())
+                .collect()
+        })
+        .collect()
+}
+
+fn boxed(plain_lines: &[String], styled_lines: &[String]) -> Vec<String> {
+    let width = plain_lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut out = vec![format!("┌{}┐", "─".repeat(width + 2))];
+    out.extend(plain_lines.iter().zip(styled_lines).map(|(plain, styled)| {
+        format!(
+            "│ {}{} │",
+            styled,
+            " ".repeat(width - plain.chars().count())
+        )
+    }));
+    out.push(format!("└{}┘", "─".repeat(width + 2)));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_riddle_elements() {
+        let html = concat!(
+            "<p>The elves <em>need</em> <a href=\"/2022/about\">help</a> ",
+            "with <span title=\"Hidden!\">snacks</span>:</p>",
+            "<pre><code>1000\n2000</code></pre>"
+        );
+
+        let rendered = render(html, 80, false, false);
+        assert_eq!(
+            rendered,
+            concat!(
+                "The elves need help[1] with snacks:\n",
+                "\n",
+                "┌──────┐\n",
+                "│ 1000 │\n",
+                "│ 2000 │\n",
+                "└──────┘\n",
+                "\n",
+                "[1] https://adventofcode.com/2022/about"
+            )
+        );
+
+        let rendered = render(html, 80, true, false);
+        assert!(rendered.starts_with("The elves need help[1] with snacks [Hidden!]:"));
+    }
+}
@@ -0,0 +1,133 @@
+use std::io::Write;
+
+use thiserror::Error;
+
+use crate::domain::{answer_check::answer_from_output, riddle_part::RiddlePart};
+
+#[derive(Error, Debug)]
+pub enum SolutionRunnerError {
+    #[error("Failed to start the solution `{0}`: {1}")]
+    Start(String, String),
+
+    #[error("The solution `{0}` failed with {1}:\n{2}")]
+    Failed(String, String, String),
+
+    #[error("The solution `{0}` printed no answer")]
+    NoAnswer(String),
+}
+
+/// Runs the user's solution of a riddle on a given input
+///
+/// The command is run by the system shell with the input on its standard
+/// input. The placeholders `{year}`, `{day}`, `{day:02}` and `{part}`
+/// are replaced in the command, and the same values are available in the
+/// `ELV_YEAR`, `ELV_DAY` and `ELV_PART` environment variables. The last
+/// non-empty line printed by the solution is its answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolutionRunner {
+    command: String,
+}
+
+impl SolutionRunner {
+    pub fn new(command: &str) -> Self {
+        SolutionRunner {
+            command: command.to_owned(),
+        }
+    }
+
+    pub fn command(&self, year: usize, day: usize, part: &RiddlePart) -> String {
+        self.command
+            .replace("{year}", &year.to_string())
+            .replace("{day:02}", &format!("{:02}", day))
+            .replace("{day}", &day.to_string())
+            .replace("{part}", &(part.clone() as u8).to_string())
+    }
+
+    pub fn run(
+        &self,
+        year: usize,
+        day: usize,
+        part: &RiddlePart,
+        input: &str,
+    ) -> Result<String, SolutionRunnerError> {
+        let command = self.command(year, day, part);
+        let mut child = Self::shell(&command)
+            .env("ELV_YEAR", year.to_string())
+            .env("ELV_DAY", day.to_string())
+            .env("ELV_PART", (part.clone() as u8).to_string())
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| SolutionRunnerError::Start(command.clone(), e.to_string()))?;
+        // The input is written while the output is read, so a solution printing
+        // a lot before reading all of its input does not block on a full pipe
+        let writer = child.stdin.take().map(|mut stdin| {
+            let input = input.to_owned();
+            std::thread::spawn(move || {
+                // The solution may exit without reading its input, which is not an error
+                let _ = stdin.write_all(input.as_bytes());
+            })
+        });
+        let output = child
+            .wait_with_output()
+            .map_err(|e| SolutionRunnerError::Start(command.clone(), e.to_string()))?;
+        if let Some(writer) = writer {
+            let _ = writer.join();
+        }
+        if !output.status.success() {
+            return Err(SolutionRunnerError::Failed(
+                command,
+                output.status.to_string(),
+                String::from_utf8_lossy(&output.stderr)
+                    .trim_end()
+                    .to_owned(),
+            ));
+        }
+        answer_from_output(&String::from_utf8_lossy(&output.stdout))
+            .ok_or(SolutionRunnerError::NoAnswer(command))
+    }
+
+    #[cfg(windows)]
+    fn shell(command: &str) -> std::process::Command {
+        let mut shell = std::process::Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    }
+
+    #[cfg(not(windows))]
+    fn shell(command: &str) -> std::process::Command {
+        let mut shell = std::process::Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_last_printed_line_is_the_answer() {
+        let runner = SolutionRunner::new("echo part {part} of {year}-{day:02}; wc -l; echo");
+        assert_eq!(
+            runner.command(2022, 5, &RiddlePart::Two),
+            "echo part 2 of 2022-05; wc -l; echo"
+        );
+        assert_eq!(
+            runner.run(2022, 5, &RiddlePart::Two, "a\nb\nc\n").unwrap(),
+            "3"
+        );
+        assert!(matches!(
+            SolutionRunner::new("exit 3").run(2022, 5, &RiddlePart::One, ""),
+            Err(SolutionRunnerError::Failed(..))
+        ));
+    }
+
+    #[test]
+    fn reads_the_output_while_writing_a_large_input() {
+        let input = "x\n".repeat(1 << 19);
+        let runner = SolutionRunner::new("cat; echo 42");
+        assert_eq!(runner.run(2022, 5, &RiddlePart::One, &input).unwrap(), "42");
+    }
+}
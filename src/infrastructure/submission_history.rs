@@ -5,7 +5,7 @@ use crate::domain::{
     submission_status::SubmissionStatus,
 };
 
-use super::configuration::Configuration;
+use super::{cli_display::CliDisplay, configuration::Configuration};
 
 #[derive(Error, Debug)]
 pub enum SubmissionHistoryError {
@@ -13,11 +13,15 @@ pub enum SubmissionHistoryError {
     Load(String),
     #[error("Error saving data to the submission history cache: {}", 0)]
     Save(String),
-    #[error("Error clearing the submission history cache: {}", 0)]
-    Clear(String),
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+/// The version of the format of the submission history files written by this version of `elv`
+///
+/// Bump it when the format changes and add the migration from the previous
+/// version to [`SubmissionHistory::migrate`].
+const HISTORY_VERSION: u64 = 1;
+
+#[derive(Debug)]
 pub struct SubmissionHistory {
     submissions: Vec<SubmissionResult>,
     year: usize,
@@ -39,25 +43,131 @@ impl SubmissionHistory {
             .find(|s| s.submission.part == *part && s.status == SubmissionStatus::Correct)
     }
 
+    /// Loads the history of the riddle from the cache
+    ///
+    /// The histories saved by the older versions of `elv` are migrated
+    /// to the current format, including the `CBOR` files without a version.
     pub fn from_cache(year: usize, day: usize) -> Result<Self, SubmissionHistoryError> {
         let cache_path = Self::cache_path(year, day);
+        let legacy_cache_path = Self::legacy_cache_path(year, day);
+        if !cache_path.exists() && legacy_cache_path.exists() {
+            return Self::migrate_legacy_cache(&legacy_cache_path);
+        }
         if !cache_path.exists() {
             Self::new(year, day).save_to_cache()?;
         }
-        let content = std::fs::read(&cache_path).map_err(|_| {
+        let (history, version) = Self::read_file(&cache_path)?;
+        if version < HISTORY_VERSION {
+            history.save_to_cache()?;
+        }
+        Ok(history)
+    }
+
+    /// Reads the history of the riddle from the cache without changing the cache
+    ///
+    /// Nothing is written, not even the migrated histories. The history
+    /// is empty if there is none in the cache.
+    pub fn read_from_cache(year: usize, day: usize) -> Result<Self, SubmissionHistoryError> {
+        let cache_path = Self::cache_path(year, day);
+        let legacy_cache_path = Self::legacy_cache_path(year, day);
+        if cache_path.exists() {
+            return Ok(Self::read_file(&cache_path)?.0);
+        }
+        if legacy_cache_path.exists() {
+            return Self::read_legacy_file(&legacy_cache_path);
+        }
+        Ok(Self::new(year, day))
+    }
+
+    /// Reads the history file and returns the history with the version of the file
+    fn read_file(cache_path: &std::path::Path) -> Result<(Self, u64), SubmissionHistoryError> {
+        let content = std::fs::read_to_string(cache_path).map_err(|_| {
             SubmissionHistoryError::Load(format!(
                 "Failed to read cache file: {}",
                 cache_path.display()
             ))
         })?;
-        serde_cbor::from_slice::<SubmissionHistory>(&content).map_err(|_| {
+        Self::decode(&content).map_err(|e| {
             SubmissionHistoryError::Load(format!(
-                "Failed to deserialize cache file: {}",
-                cache_path.display()
+                "Failed to deserialize cache file {}: {}",
+                cache_path.display(),
+                e
             ))
         })
     }
 
+    fn read_legacy_file(
+        legacy_cache_path: &std::path::Path,
+    ) -> Result<Self, SubmissionHistoryError> {
+        let content = std::fs::read(legacy_cache_path).map_err(|_| {
+            SubmissionHistoryError::Load(format!(
+                "Failed to read cache file: {}",
+                legacy_cache_path.display()
+            ))
+        })?;
+        Self::decode_legacy(&content).map_err(|e| {
+            SubmissionHistoryError::Load(format!(
+                "Failed to deserialize cache file {}: {}",
+                legacy_cache_path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Converts the unversioned `CBOR` history to the current format and removes the old file
+    fn migrate_legacy_cache(
+        legacy_cache_path: &std::path::Path,
+    ) -> Result<Self, SubmissionHistoryError> {
+        let history = Self::read_legacy_file(legacy_cache_path)?;
+        history.save_to_cache()?;
+        std::fs::remove_file(legacy_cache_path).map_err(|_| {
+            SubmissionHistoryError::Save(format!(
+                "Failed to remove the migrated cache file: {}",
+                legacy_cache_path.display()
+            ))
+        })?;
+        Ok(history)
+    }
+
+    /// Reads the history in any supported version and returns it with the version of the file
+    fn decode(content: &str) -> Result<(Self, u64), String> {
+        let value: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        let version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or("The file does not say its version")?;
+        if version > HISTORY_VERSION {
+            return Err(format!(
+                "The file was written by a newer version of elv (format version {}). Update elv to read it",
+                version
+            ));
+        }
+        let stored: StoredHistory =
+            serde_json::from_value(Self::migrate(value, version)?).map_err(|e| e.to_string())?;
+        Ok((stored.into(), version))
+    }
+
+    /// Upgrades the history from the given version of the format to the current one
+    fn migrate(value: serde_json::Value, version: u64) -> Result<serde_json::Value, String> {
+        match version {
+            HISTORY_VERSION => Ok(value),
+            _ => Err(format!("Unknown format version {}", version)),
+        }
+    }
+
+    fn decode_legacy(content: &[u8]) -> Result<Self, String> {
+        let legacy: LegacyHistory = serde_cbor::from_slice(content).map_err(|e| e.to_string())?;
+        Ok(legacy.into())
+    }
+
+    fn encode(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&StoredHistory::from(self))
+    }
+
+    pub fn submissions(&self) -> &[SubmissionResult] {
+        &self.submissions
+    }
+
     pub fn add(&mut self, submission: SubmissionResult) {
         self.submissions.push(submission);
     }
@@ -81,9 +191,13 @@ impl SubmissionHistory {
         }
     }
 
+    /// Finds the verdict on the same answer sent before
+    ///
+    /// The answers sent too soon were never checked by the servers, so they are skipped.
     pub fn get_result_for_submission(&self, submission: &Submission) -> Option<&SubmissionResult> {
         self.submissions
             .iter()
+            .filter(|s| s.status != SubmissionStatus::TooSoon)
             .find(|&s| s.submission.eq(submission))
     }
 
@@ -98,7 +212,7 @@ impl SubmissionHistory {
                 ))
             })?;
         }
-        let serialized = serde_cbor::to_vec(&self).map_err(|_| {
+        let serialized = self.encode().map_err(|_| {
             SubmissionHistoryError::Save(format!(
                 "Failed to serialize cache file: {}",
                 cache_path.display()
@@ -114,22 +228,15 @@ impl SubmissionHistory {
         Ok(())
     }
 
-    pub fn clear() -> Result<(), SubmissionHistoryError> {
-        let cache_dir = Configuration::get_project_directories()
+    pub(crate) fn cache_path(year: usize, day: usize) -> std::path::PathBuf {
+        Configuration::get_project_directories()
             .cache_dir()
-            .join("submissions");
-        if cache_dir.exists() {
-            std::fs::remove_dir_all(&cache_dir).map_err(|_| {
-                SubmissionHistoryError::Clear(format!(
-                    "Failed to remove cache directory: {}",
-                    cache_dir.display()
-                ))
-            })?
-        }
-        Ok(())
+            .join("submissions")
+            .join(format!("{}-{}.json", year, day))
     }
 
-    fn cache_path(year: usize, day: usize) -> std::path::PathBuf {
+    /// The path of the `CBOR` history written by the versions of `elv` before the format had a version
+    fn legacy_cache_path(year: usize, day: usize) -> std::path::PathBuf {
         Configuration::get_project_directories()
             .cache_dir()
             .join("submissions")
@@ -144,6 +251,151 @@ impl SubmissionHistory {
     }
 }
 
+/// The submission history as it is written to the cache
+///
+/// It is kept apart from the domain types, so changing them does not break
+/// the histories saved before.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct StoredHistory {
+    version: u64,
+    year: usize,
+    day: usize,
+    submissions: Vec<StoredSubmission>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct StoredSubmission {
+    part: RiddlePart,
+    answer: String,
+    status: SubmissionStatus,
+    message: String,
+    submitted_at: chrono::DateTime<chrono::Utc>,
+    wait_time_secs: u64,
+}
+
+impl From<&SubmissionHistory> for StoredHistory {
+    fn from(history: &SubmissionHistory) -> Self {
+        StoredHistory {
+            version: HISTORY_VERSION,
+            year: history.year,
+            day: history.day,
+            submissions: history
+                .submissions
+                .iter()
+                .map(|result| StoredSubmission {
+                    part: result.submission.part.clone(),
+                    answer: result.submission.answer.clone(),
+                    status: result.status.clone(),
+                    message: result.message.clone(),
+                    submitted_at: result.submitted_at,
+                    wait_time_secs: result.wait_time.as_secs(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<StoredHistory> for SubmissionHistory {
+    fn from(stored: StoredHistory) -> Self {
+        SubmissionHistory {
+            submissions: stored
+                .submissions
+                .into_iter()
+                .map(|submission| {
+                    SubmissionResult::new(
+                        Submission::new(
+                            submission.part,
+                            submission.answer,
+                            stored.year,
+                            stored.day,
+                        ),
+                        submission.status,
+                        submission.message,
+                        submission.submitted_at,
+                        std::time::Duration::from_secs(submission.wait_time_secs),
+                    )
+                })
+                .collect(),
+            year: stored.year,
+            day: stored.day,
+        }
+    }
+}
+
+/// The unversioned `CBOR` submission history written by the older versions of `elv`
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct LegacyHistory {
+    submissions: Vec<LegacySubmissionResult>,
+    year: usize,
+    day: usize,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct LegacySubmissionResult {
+    submission: LegacySubmission,
+    status: SubmissionStatus,
+    message: String,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    submitted_at: chrono::DateTime<chrono::Utc>,
+    wait_time: std::time::Duration,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct LegacySubmission {
+    part: RiddlePart,
+    answer: String,
+    year: usize,
+    day: usize,
+}
+
+impl From<LegacyHistory> for SubmissionHistory {
+    fn from(legacy: LegacyHistory) -> Self {
+        SubmissionHistory {
+            submissions: legacy
+                .submissions
+                .into_iter()
+                .map(|result| {
+                    SubmissionResult::new(
+                        Submission::new(
+                            result.submission.part,
+                            result.submission.answer,
+                            result.submission.year,
+                            result.submission.day,
+                        ),
+                        result.status,
+                        result.message,
+                        result.submitted_at,
+                        result.wait_time,
+                    )
+                })
+                .collect(),
+            year: legacy.year,
+            day: legacy.day,
+        }
+    }
+}
+
+impl CliDisplay for SubmissionHistory {
+    fn cli_fmt(&self, _configuration: &Configuration) -> String {
+        if self.submissions.is_empty() {
+            return format!("No submissions for {}-{:02}", self.year, self.day);
+        }
+        self.submissions
+            .iter()
+            .map(|result| {
+                format!(
+                    "{} part {} {:?}: {}",
+                    result.submitted_at.format("%Y-%m-%d %H:%M:%S"),
+                    result.submission.part,
+                    result.status,
+                    result.submission.answer
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::domain::{
@@ -151,7 +403,9 @@ mod tests {
         submission_status::SubmissionStatus,
     };
 
-    use super::SubmissionHistory;
+    use super::{
+        LegacyHistory, LegacySubmission, LegacySubmissionResult, SubmissionHistory, HISTORY_VERSION,
+    };
 
     #[test]
     fn can_add_submission() {
@@ -207,6 +461,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn the_history_survives_the_round_trip() {
+        let submitted_at = chrono::DateTime::parse_from_rfc3339("2022-12-01T05:00:07Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let mut submission_history = SubmissionHistory::new(2022, 1);
+        submission_history.add(SubmissionResult::new(
+            Submission::new(RiddlePart::Two, "a&b".to_string(), 2022, 1),
+            SubmissionStatus::Incorrect,
+            "That's not the right answer.".to_string(),
+            submitted_at,
+            std::time::Duration::from_secs(60),
+        ));
+
+        let encoded = submission_history.encode().unwrap();
+        assert!(encoded.contains("\"version\": 1"));
+        assert!(encoded.contains("\"submitted_at\": \"2022-12-01T05:00:07Z\""));
+        let (decoded, version) = SubmissionHistory::decode(&encoded).unwrap();
+        assert_eq!(version, HISTORY_VERSION);
+        assert_eq!(decoded.submissions, submission_history.submissions);
+        assert!(
+            SubmissionHistory::decode(&encoded.replace("\"version\": 1", "\"version\": 99"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn the_unversioned_cbor_history_is_migrated() {
+        let legacy = LegacyHistory {
+            submissions: vec![LegacySubmissionResult {
+                submission: LegacySubmission {
+                    part: RiddlePart::One,
+                    answer: "7".to_string(),
+                    year: 2020,
+                    day: 1,
+                },
+                status: SubmissionStatus::Correct,
+                message: "That's the right answer!".to_string(),
+                submitted_at: chrono::Utc::now(),
+                wait_time: std::time::Duration::from_secs(5),
+            }],
+            year: 2020,
+            day: 1,
+        };
+
+        let migrated =
+            SubmissionHistory::decode_legacy(&serde_cbor::to_vec(&legacy).unwrap()).unwrap();
+        assert_eq!(
+            migrated
+                .correct_submission(&RiddlePart::One)
+                .unwrap()
+                .submission,
+            Submission::new(RiddlePart::One, "7".to_string(), 2020, 1)
+        );
+    }
+
+    #[test]
+    fn answers_sent_too_soon_are_not_repeated_submissions() {
+        let submission = Submission::new(RiddlePart::One, "7".to_string(), 2020, 1);
+        let message = concat!(
+            "You gave an answer too recently; you have to wait after submitting an answer ",
+            "before trying again. You have 30s left to wait. [Return to Day 1]"
+        );
+        let wait_time =
+            crate::infrastructure::aoc_api::AocApi::extract_wait_time_from_message(message);
+        assert_eq!(wait_time, std::time::Duration::from_secs(30));
+        let mut submission_history = SubmissionHistory::new(2020, 1);
+        submission_history.add(SubmissionResult::new(
+            submission.clone(),
+            SubmissionStatus::TooSoon,
+            message.to_string(),
+            chrono::Utc::now(),
+            wait_time,
+        ));
+        assert_eq!(
+            submission_history.get_result_for_submission(&submission),
+            None
+        );
+        assert!(submission_history
+            .wait_time(&chrono::Utc::now(), &RiddlePart::One)
+            .is_some());
+    }
+
     #[test]
     fn test_wait_time() {
         let now = chrono::Utc::now();
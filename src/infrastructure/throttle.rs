@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use super::configuration::{Configuration, ThrottleConfiguration};
+use crate::domain::duration_string::DurationString;
+
+#[derive(Error, Debug)]
+pub enum ThrottleError {
+    #[error("{0}")]
+    TooManyRequests(String),
+}
+
+/// The groups of the Advent of Code endpoints, each throttled separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EndpointClass {
+    Page,
+    Input,
+    Answer,
+    PrivateLeaderboard,
+}
+
+impl EndpointClass {
+    /// Tells whether sending the same request twice is harmless
+    ///
+    /// Only the answers are not - each of them counts as a guess.
+    pub fn is_idempotent(&self) -> bool {
+        !matches!(self, EndpointClass::Answer)
+    }
+
+    fn configuration_key(&self) -> &'static str {
+        match self {
+            EndpointClass::Page => "throttle.pages",
+            EndpointClass::Input => "throttle.inputs",
+            EndpointClass::Answer => "throttle.answers",
+            EndpointClass::PrivateLeaderboard => "throttle.private_leaderboards",
+        }
+    }
+}
+
+impl std::fmt::Display for EndpointClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EndpointClass::Page => write!(f, "pages"),
+            EndpointClass::Input => write!(f, "inputs"),
+            EndpointClass::Answer => write!(f, "answers"),
+            EndpointClass::PrivateLeaderboard => write!(f, "private leaderboards"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct EndpointState {
+    last_request: Option<chrono::DateTime<chrono::Utc>>,
+    blocked_until: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+type ThrottleState = HashMap<EndpointClass, EndpointState>;
+
+/// Spaces out the requests to the Advent of Code servers
+///
+/// The time of the last request is kept in the cache directory,
+/// so the spacing holds across many invocations of `elv`. The state
+/// is locked while it is updated, so it holds for the invocations
+/// running at the same time, too.
+#[derive(Debug)]
+pub struct Throttle {
+    configuration: ThrottleConfiguration,
+    state_path: PathBuf,
+}
+
+impl Throttle {
+    pub fn new(configuration: ThrottleConfiguration) -> Self {
+        Self {
+            configuration,
+            state_path: Configuration::get_project_directories()
+                .cache_dir()
+                .join("throttle.json"),
+        }
+    }
+
+    /// Waits until a request to the endpoints is allowed and records it
+    pub fn acquire(&self, class: EndpointClass) -> Result<(), ThrottleError> {
+        let wait = {
+            let _lock = self.lock();
+            let mut state = self.load();
+            let now = chrono::Utc::now();
+            let wait = self.wait_time(state.get(&class), class, &now)?;
+            // The time of the request is reserved before waiting for it,
+            // so the other invocations wait for the next one
+            state.entry(class).or_default().last_request = Some(now + wait);
+            self.save(&state);
+            wait
+        };
+        if wait.num_seconds() > 0 {
+            eprintln!(
+                "Waiting {} before the next request for {}...",
+                DurationString::new(wait),
+                class
+            );
+        }
+        std::thread::sleep(wait.to_std().unwrap_or_default());
+        Ok(())
+    }
+
+    /// Records that the servers asked not to request the endpoints for a while
+    pub fn block(&self, class: EndpointClass, duration: chrono::Duration) {
+        let _lock = self.lock();
+        let mut state = self.load();
+        state.entry(class).or_default().blocked_until = Some(chrono::Utc::now() + duration);
+        self.save(&state);
+    }
+
+    pub fn max_wait(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.configuration.max_wait as i64)
+    }
+
+    fn spacing(&self, class: EndpointClass) -> chrono::Duration {
+        chrono::Duration::seconds(match class {
+            EndpointClass::Page => self.configuration.pages,
+            EndpointClass::Input => self.configuration.inputs,
+            EndpointClass::Answer => self.configuration.answers,
+            EndpointClass::PrivateLeaderboard => self.configuration.private_leaderboards,
+        } as i64)
+    }
+
+    fn wait_time(
+        &self,
+        state: Option<&EndpointState>,
+        class: EndpointClass,
+        now: &chrono::DateTime<chrono::Utc>,
+    ) -> Result<chrono::Duration, ThrottleError> {
+        let state = match state {
+            Some(state) => state,
+            None => return Ok(chrono::Duration::zero()),
+        };
+        let until_unblocked = state
+            .blocked_until
+            .map_or(chrono::Duration::zero(), |until| until - *now);
+        if until_unblocked > self.max_wait() {
+            return Err(ThrottleError::TooManyRequests(format!(
+                "The Advent of Code servers asked elv to slow down the requests for {}. Try again in {}",
+                class,
+                DurationString::new(until_unblocked)
+            )));
+        }
+        let until_spaced = state.last_request.map_or(chrono::Duration::zero(), |last| {
+            last + self.spacing(class) - *now
+        });
+        if until_spaced > self.max_wait() {
+            return Err(ThrottleError::TooManyRequests(format!(
+                "elv requests {} at most once every {}. Try again in {} or change the `{}` configuration value",
+                class,
+                DurationString::new(self.spacing(class)),
+                DurationString::new(until_spaced),
+                class.configuration_key()
+            )));
+        }
+        Ok(until_unblocked
+            .max(until_spaced)
+            .max(chrono::Duration::zero()))
+    }
+
+    fn load(&self) -> ThrottleState {
+        std::fs::read_to_string(&self.state_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, state: &ThrottleState) {
+        // The throttle is best effort - failing to write its state
+        // should not prevent the request from being sent
+        if let Ok(contents) = serde_json::to_string(state) {
+            // The state is renamed into place, so it is never read half-written
+            let draft_path = self
+                .state_path
+                .with_extension(format!("{}.tmp", std::process::id()));
+            if std::fs::write(&draft_path, contents).is_ok()
+                && std::fs::rename(&draft_path, &self.state_path).is_err()
+            {
+                std::fs::remove_file(&draft_path).ok();
+            }
+        }
+    }
+
+    /// Locks the state until the returned file is dropped
+    ///
+    /// The throttle works without the lock if the lock file cannot be created.
+    fn lock(&self) -> Option<std::fs::File> {
+        if let Some(parent) = self.state_path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        let lock_file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.state_path.with_extension("lock"))
+            .ok()?;
+        lock_file.lock().ok()?;
+        Some(lock_file)
+    }
+}
+
+/// Parses the value of the `Retry-After` header
+///
+/// The header holds either the number of seconds to wait or an HTTP date.
+pub fn parse_retry_after(
+    value: &str,
+    now: &chrono::DateTime<chrono::Utc>,
+) -> Option<chrono::Duration> {
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(chrono::Duration::seconds(seconds as i64));
+    }
+    chrono::DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|date| (date.with_timezone(&chrono::Utc) - *now).max(chrono::Duration::zero()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn throttle() -> Throttle {
+        Throttle {
+            configuration: ThrottleConfiguration::default(),
+            state_path: PathBuf::from("throttle.json"),
+        }
+    }
+
+    #[test]
+    fn waits_for_the_spacing_between_requests() {
+        let now = chrono::Utc.with_ymd_and_hms(2022, 12, 1, 12, 0, 0).unwrap();
+        let state = EndpointState {
+            last_request: Some(now - chrono::Duration::milliseconds(200)),
+            blocked_until: None,
+        };
+        let wait = throttle()
+            .wait_time(Some(&state), EndpointClass::Page, &now)
+            .unwrap();
+        assert_eq!(wait, chrono::Duration::milliseconds(800));
+
+        let wait = throttle()
+            .wait_time(None, EndpointClass::Page, &now)
+            .unwrap();
+        assert_eq!(wait, chrono::Duration::zero());
+    }
+
+    #[test]
+    fn fails_if_the_wait_is_too_long() {
+        let now = chrono::Utc.with_ymd_and_hms(2022, 12, 1, 12, 0, 0).unwrap();
+        let state = EndpointState {
+            last_request: Some(now - chrono::Duration::minutes(1)),
+            blocked_until: None,
+        };
+        assert!(throttle()
+            .wait_time(Some(&state), EndpointClass::PrivateLeaderboard, &now)
+            .is_err());
+
+        let state = EndpointState {
+            last_request: None,
+            blocked_until: Some(now + chrono::Duration::minutes(5)),
+        };
+        assert!(throttle()
+            .wait_time(Some(&state), EndpointClass::Input, &now)
+            .is_err());
+    }
+
+    #[test]
+    fn waits_for_the_other_invocations_to_update_the_state() {
+        let state_path = std::env::temp_dir()
+            .join("elv-throttle-test")
+            .join("throttle.json");
+        let throttle = Throttle {
+            configuration: ThrottleConfiguration::default(),
+            state_path: state_path.clone(),
+        };
+        let lock = throttle.lock().expect("The lock file should be created");
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let other_invocation = std::thread::spawn(move || {
+            let throttle = Throttle {
+                configuration: ThrottleConfiguration::default(),
+                state_path,
+            };
+            sender.send(throttle.acquire(EndpointClass::Page)).unwrap();
+        });
+        assert!(receiver
+            .recv_timeout(std::time::Duration::from_millis(200))
+            .is_err());
+
+        drop(lock);
+        assert!(receiver.recv().unwrap().is_ok());
+        other_invocation.join().unwrap();
+        std::fs::remove_dir_all(std::env::temp_dir().join("elv-throttle-test")).unwrap();
+    }
+
+    #[test]
+    fn parses_the_retry_after_header() {
+        let now = chrono::Utc
+            .with_ymd_and_hms(2015, 10, 21, 7, 27, 0)
+            .unwrap();
+        assert_eq!(
+            parse_retry_after("120", &now),
+            Some(chrono::Duration::seconds(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", &now),
+            Some(chrono::Duration::seconds(60))
+        );
+        assert_eq!(parse_retry_after("soon", &now), None);
+    }
+}
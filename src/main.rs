@@ -1,5 +1,5 @@
 use elv::ElvCli;
 
-fn main() {
-    ElvCli::run();
+fn main() -> std::process::ExitCode {
+    ElvCli::run()
 }